@@ -0,0 +1,55 @@
+#import bevy_pbr::mesh_view_bindings
+
+@group(1) @binding(0)
+var base_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var base_sampler: sampler;
+// the brightness is in x, the rest is padding
+@group(1) @binding(2)
+var<uniform> brightness: vec4<f32>;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// value noise, kept to a few octaves so it stays cheap on WebGL
+fn noise(p: vec2<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+
+    return mix(
+        mix(hash(i), hash(i + vec2<f32>(1.0, 0.0)), u.x),
+        mix(hash(i + vec2<f32>(0.0, 1.0)), hash(i + vec2<f32>(1.0, 1.0)), u.x),
+        u.y
+    );
+}
+
+fn turbulence(p: vec2<f32>, time: f32) -> f32 {
+    var value = 0.0;
+    var amplitude = 0.5;
+    var point = p;
+
+    for (var octave = 0; octave < 3; octave = octave + 1) {
+        value = value + amplitude * abs(noise(point + time * 0.1) * 2.0 - 1.0);
+        point = point * 2.0;
+        amplitude = amplitude * 0.5;
+    }
+
+    return value;
+}
+
+@fragment
+fn fragment(
+    #import bevy_pbr::mesh_vertex_output
+) -> @location(0) vec4<f32> {
+    let time = globals.time;
+    let plasma = turbulence(uv * vec2<f32>(32.0, 16.0), time);
+
+    // wobble where the texture is sampled so the surface itself seems to flow
+    let warped = uv + (vec2<f32>(plasma, plasma) - 0.5) * 0.01;
+    let base = textureSample(base_texture, base_sampler, warped).rgb;
+
+    let hot = vec3<f32>(1.0, 0.9, 0.6);
+    return vec4<f32>(mix(base, hot, plasma * 0.5) * (1.0 + plasma) * brightness.x, 1.0);
+}
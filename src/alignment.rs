@@ -0,0 +1,145 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    orbital_angle,
+    simulation::{step_orbits, OrbitState, SimulationClock, SIMULATION_STEP},
+    space::{BodyDatabase, Category, ExaggerationSettings, SpaceObject},
+};
+
+/// How tightly the planets have to bunch up around the Sun to count as aligned, in degrees.
+const ALIGNMENT_SPREAD: f32 = 40.0;
+
+/// How long the alignment notice stays up, in seconds.
+const NOTICE_DURATION: f32 = 5.0;
+
+/// How far ahead "find next alignment" looks before giving up, in simulation steps.
+const MAX_SEARCH_STEPS: u32 = 200_000;
+
+/// Tracks planetary alignments ("planet parades") as the simulation runs.
+#[derive(Resource, Default)]
+pub struct Alignment {
+    /// The smallest angle containing every planet, as of the last frame, in degrees.
+    spread: f32,
+    aligned: bool,
+    /// How much longer to show the notice for.
+    notice: f32,
+    search_requested: bool,
+    search_failed: bool,
+}
+
+fn is_planet(obj: SpaceObject) -> bool {
+    matches!(
+        obj.category(),
+        Category::Terrestrial | Category::GasGiant | Category::IceGiant
+    )
+}
+
+// the smallest arc around the Sun that every planet fits in, which is everything but the
+// biggest gap between neighbouring planets
+fn angular_spread(planet_q: &Query<(&mut OrbitState, &SpaceObject)>) -> f32 {
+    let mut angles: Vec<f32> = planet_q
+        .iter()
+        .filter(|(_, obj)| is_planet(**obj))
+        .map(|(state, _)| orbital_angle(state.current).rem_euclid(TAU))
+        .collect();
+    if angles.len() < 2 {
+        return 0.0;
+    }
+    angles.sort_by(f32::total_cmp);
+
+    let wrap_gap = angles[0] + TAU - angles[angles.len() - 1];
+    let biggest_gap = angles
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .fold(wrap_gap, f32::max);
+
+    (TAU - biggest_gap).to_degrees()
+}
+
+pub fn detect_alignment(
+    time: Res<Time>,
+    mut alignment: ResMut<Alignment>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    mut clock: ResMut<SimulationClock>,
+    mut planet_q: Query<(&mut OrbitState, &SpaceObject)>,
+) {
+    if alignment.search_requested {
+        alignment.search_requested = false;
+
+        // step past the current alignment first, so the button always finds a new one
+        let mut left_alignment = angular_spread(&planet_q) > ALIGNMENT_SPREAD;
+        let mut found = false;
+        for _ in 0..MAX_SEARCH_STEPS {
+            step_orbits(
+                &mut planet_q,
+                &db,
+                &exaggeration,
+                &mut clock,
+                SIMULATION_STEP,
+            );
+            let aligned = angular_spread(&planet_q) <= ALIGNMENT_SPREAD;
+            if aligned && left_alignment {
+                found = true;
+                break;
+            }
+            left_alignment |= !aligned;
+        }
+
+        // don't interpolate across the jump
+        for (mut state, _) in planet_q.iter_mut() {
+            state.previous = state.current;
+        }
+        alignment.search_failed = !found;
+    }
+
+    alignment.spread = angular_spread(&planet_q);
+    let aligned = alignment.spread <= ALIGNMENT_SPREAD;
+    if aligned && !alignment.aligned {
+        alignment.notice = NOTICE_DURATION;
+    }
+    alignment.aligned = aligned;
+    alignment.notice = (alignment.notice - time.delta_seconds()).max(0.0);
+}
+
+pub fn alignment_ui(mut egui_ctx: ResMut<EguiContext>, mut alignment: ResMut<Alignment>) {
+    if alignment.notice > 0.0 {
+        // blink a couple of times a second so it catches the eye
+        let visible = (alignment.notice * 4.0) as u32 % 2 == 0;
+        egui::Area::new("alignment notice")
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 32.0))
+            .show(egui_ctx.ctx_mut(), |ui| {
+                let color = if visible {
+                    egui::Color32::GOLD
+                } else {
+                    egui::Color32::from_rgb(160, 130, 0)
+                };
+                ui.label(
+                    egui::RichText::new("Planetary alignment!")
+                        .size(28.0)
+                        .color(color),
+                );
+            });
+    }
+
+    egui::Window::new("Planet parade")
+        .default_open(false)
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.label(format!(
+                "The planets are spread over {:.0}° around the Sun.",
+                alignment.spread
+            ));
+            ui.label(format!(
+                "They count as aligned within {ALIGNMENT_SPREAD:.0}°."
+            ));
+            if ui.button("Find next alignment").clicked() {
+                alignment.search_requested = true;
+            }
+            if alignment.search_failed {
+                ui.label("No alignment coming up any time soon.");
+            }
+        });
+}
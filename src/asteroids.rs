@@ -0,0 +1,172 @@
+use bevy::{pbr::NotShadowCaster, prelude::*};
+
+use crate::{
+    simulation::SimulationClock,
+    space::{eccentric_anomaly, BodyDatabase, ExaggerationSettings, SpaceObject},
+};
+
+/// Whether the asteroid belt between Mars and Jupiter is drawn. It's only for looks, so it can be
+/// turned off on slow machines.
+#[derive(Resource)]
+pub struct ShowAsteroidBelt(pub bool);
+
+impl Default for ShowAsteroidBelt {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// One rock in the belt, moving round the Sun on its own small ellipse.
+#[derive(Component)]
+pub struct Asteroid {
+    /// Half the longest width of its orbit, in AU.
+    semi_major_axis: f32,
+    eccentricity: f32,
+    /// How far round its orbit it was at the start, in radians.
+    phase: f32,
+    /// The rotation from the ecliptic to the plane of its orbit.
+    plane: Quat,
+    /// Its radius in kilometers.
+    radius: f32,
+}
+
+/// How many rocks there are. They all share one mesh and material, but each is still an entity
+/// to move every frame, so the web gets fewer.
+#[cfg(not(target_arch = "wasm32"))]
+const ASTEROID_COUNT: usize = 3000;
+#[cfg(target_arch = "wasm32")]
+const ASTEROID_COUNT: usize = 800;
+
+/// Where the main belt starts and ends, in AU. Mars is at about 1.5 and Jupiter at about 5.2.
+const INNER_EDGE: f32 = 2.2;
+const OUTER_EDGE: f32 = 3.3;
+
+/// The most the rocks' orbits are stretched, and tilted from the ecliptic in degrees.
+const MAX_ECCENTRICITY: f32 = 0.15;
+const MAX_INCLINATION: f32 = 10.0;
+
+/// The smallest and biggest rock, in kilometers. Ceres is about as big as the biggest.
+const MIN_RADIUS: f32 = 30.0;
+const MAX_RADIUS: f32 = 450.0;
+
+/// The belt comes out the same every time.
+const SEED: u64 = 0x5eed_a57e_501d;
+
+// xorshift, which is plenty random for scattering rocks
+struct Rng(u64);
+
+impl Rng {
+    /// A number from 0 up to 1.
+    fn fraction(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.fraction()
+    }
+}
+
+// scatter the rocks through the belt when it's turned on, and clear them away when it's off
+pub fn spawn_asteroid_belt(
+    mut commands: Commands,
+    show: Res<ShowAsteroidBelt>,
+    asteroids: Query<Entity, With<Asteroid>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !show.is_changed() {
+        return;
+    }
+
+    for entity in asteroids.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if !show.0 {
+        return;
+    }
+
+    // a lumpy little ball, shared by every rock so they're cheap to draw
+    let mesh = meshes.add(Mesh::from(shape::Icosphere {
+        radius: 1.0,
+        subdivisions: 1,
+    }));
+    let material = materials.add(StandardMaterial {
+        base_color: Color::rgb(0.45, 0.42, 0.4),
+        perceptual_roughness: 1.0,
+        ..default()
+    });
+
+    let mut rng = Rng(SEED);
+    let rocks: Vec<_> = (0..ASTEROID_COUNT)
+        .map(|_| {
+            let asteroid = Asteroid {
+                semi_major_axis: rng.range(INNER_EDGE, OUTER_EDGE),
+                eccentricity: rng.range(0.0, MAX_ECCENTRICITY),
+                phase: rng.range(0.0, std::f32::consts::TAU),
+                plane: Quat::from_rotation_y(rng.range(0.0, std::f32::consts::TAU))
+                    * Quat::from_rotation_x(
+                        rng.range(-MAX_INCLINATION, MAX_INCLINATION).to_radians(),
+                    ),
+                // mostly small ones, like the real belt
+                radius: MIN_RADIUS + (MAX_RADIUS - MIN_RADIUS) * rng.fraction().powi(4),
+            };
+            (
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material: material.clone(),
+                    ..default()
+                },
+                // thousands of shadows would cost far more than they add
+                NotShadowCaster,
+                asteroid,
+            )
+        })
+        .collect();
+    commands.spawn_batch(rocks);
+}
+
+// move every rock round its orbit, at the speed a planet that far out would go
+pub fn move_asteroids(
+    clock: Res<SimulationClock>,
+    exaggeration: Res<ExaggerationSettings>,
+    db: Res<BodyDatabase>,
+    mut asteroids: Query<(&Asteroid, &mut Transform)>,
+) {
+    if asteroids.is_empty() {
+        return;
+    }
+
+    let elapsed = clock.shown_elapsed();
+    // speeds fall off as one over the square root of the distance, like the planets'
+    let mars = SpaceObject::Mars;
+    let mars_speed =
+        mars.orbital_velocity(&db) as f64 * (mars.distance_from_parent(&db) as f64).sqrt();
+    // sizes are exaggerated like Earth's, distances like everything else's
+    let scene_units_per_km =
+        SpaceObject::Earth.scaled_radius(&db, &exaggeration) / SpaceObject::Earth.radius(&db);
+    let scene_units_per_au = SpaceObject::scaled_au(1.0, &exaggeration);
+
+    for (asteroid, mut transform) in asteroids.iter_mut() {
+        let speed = mars_speed / (asteroid.semi_major_axis as f64).sqrt();
+        let mean_anomaly =
+            (asteroid.phase as f64 + speed * elapsed).rem_euclid(std::f64::consts::TAU) as f32;
+
+        let anomaly = eccentric_anomaly(mean_anomaly, asteroid.eccentricity);
+        let (sin, cos) = anomaly.sin_cos();
+        let semi_major_axis = asteroid.semi_major_axis * scene_units_per_au;
+        let semi_minor_axis =
+            semi_major_axis * (1.0 - asteroid.eccentricity * asteroid.eccentricity).sqrt();
+        // the same way round as `SpaceObject::orbit_position`
+        let from_sun = Vec3::new(
+            semi_major_axis * (cos - asteroid.eccentricity),
+            0.0,
+            -semi_minor_axis * sin,
+        );
+
+        transform.translation = asteroid.plane * from_sun;
+        transform.scale = Vec3::splat(asteroid.radius * scene_units_per_km);
+    }
+}
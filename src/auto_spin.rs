@@ -0,0 +1,73 @@
+use bevy::{
+    input::{
+        gamepad::GamepadEvent,
+        mouse::{MouseMotion, MouseWheel},
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gamepad::gamepad_input,
+    lock::lock_to_object,
+    selection::CurrentObject,
+    settings::{Persisted, PersistedAppExt},
+    CameraOrbit,
+};
+
+/// Circling the camera around the selected object while the app is left alone.
+pub struct AutoSpinPlugin;
+
+impl Plugin for AutoSpinPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_persisted_resource::<AutoSpin>()
+            .add_system(auto_spin.after(gamepad_input).before(lock_to_object));
+    }
+}
+
+/// Whether to slowly spin the camera around the selected object once nobody's touched anything
+/// for a while, for showing the app off unattended.
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct AutoSpin(pub bool);
+
+impl Persisted for AutoSpin {
+    const KEY: &'static str = "auto_spin";
+}
+
+/// How long to wait without any input before [`AutoSpin`] kicks in, in seconds.
+const AUTO_SPIN_DELAY: f32 = 5.0;
+
+/// How long one full turn around the object takes while spinning, in seconds.
+const AUTO_SPIN_PERIOD: f32 = 20.0;
+
+// circle the selected object once there's been no input for a while, stopping as soon as there is
+#[allow(clippy::too_many_arguments)]
+fn auto_spin(
+    time: Res<Time>,
+    spin: Res<AutoSpin>,
+    kbd: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    mut wheel: EventReader<MouseWheel>,
+    mut gamepad: EventReader<GamepadEvent>,
+    touches: Res<Touches>,
+    current: Query<(), With<CurrentObject>>,
+    mut orbit: ResMut<CameraOrbit>,
+    mut idle: Local<f32>,
+) {
+    // read every event, so old ones don't count as input the next time this is turned on
+    let moved = motion.iter().count() + wheel.iter().count() + gamepad.iter().count() > 0;
+    let touched = kbd.get_pressed().next().is_some()
+        || mouse.get_pressed().next().is_some()
+        || touches.iter().next().is_some();
+
+    if moved || touched || current.is_empty() {
+        *idle = 0.0;
+        return;
+    }
+
+    *idle += time.delta_seconds();
+    if spin.0 && *idle > AUTO_SPIN_DELAY {
+        orbit.yaw += std::f32::consts::TAU / AUTO_SPIN_PERIOD * time.delta_seconds();
+    }
+}
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    camera::world_to_screen,
+    selection::CurrentObject,
+    space::{BodyDatabase, SpaceObject},
+    DisplayRadius,
+};
+
+/// Whether the selected object shows the axis it spins around.
+#[derive(Resource, Default)]
+pub struct ShowAxes(pub bool);
+
+/// How far the axis sticks out past each pole, as a multiple of the object's radius.
+const AXIS_LENGTH: f32 = 1.6;
+
+// draw a line through the selected object's poles, labelled so the spin direction can be read off
+pub fn rotation_axis(
+    mut egui_ctx: ResMut<EguiContext>,
+    show: Res<ShowAxes>,
+    db: Res<BodyDatabase>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    current: Query<(&SpaceObject, &GlobalTransform, &DisplayRadius), With<CurrentObject>>,
+) {
+    if !show.0 {
+        return;
+    }
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    let Ok((obj, transform, display_radius)) = current.get_single() else {
+        return;
+    };
+
+    let center = transform.translation();
+    let axis = obj.rotation_axis(&db) * display_radius.0 * AXIS_LENGTH;
+    let to_screen = |point: Vec3| world_to_screen(camera, camera_transform, point);
+    let (Some(north), Some(south)) = (to_screen(center + axis), to_screen(center - axis)) else {
+        return;
+    };
+
+    let painter = egui_ctx
+        .ctx_mut()
+        .layer_painter(egui::LayerId::background());
+    painter.line_segment(
+        [north, south],
+        egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 200, 80)),
+    );
+    for (pole, other, label) in [(north, south, "N"), (south, north, "S")] {
+        // push the label a little past the end of the line, unless it's being looked at end-on
+        let outwards = pole - other;
+        let offset = if outwards.length() > 1.0 {
+            outwards.normalized() * 10.0
+        } else {
+            egui::Vec2::ZERO
+        };
+        painter.text(
+            pole + offset,
+            egui::Align2::CENTER_CENTER,
+            label,
+            egui::FontId::proportional(14.0),
+            egui::Color32::WHITE,
+        );
+    }
+}
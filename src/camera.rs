@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use bevy_dolly::{dolly::glam, prelude::*};
+use bevy_egui::egui;
+
+use crate::{space::SpaceObject, DisplayRadius};
+
+/// How the camera rig eases between positions.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct CameraTuning {
+    /// Smoothing of the first, predictive position smoother, in seconds.
+    pub position_smoothness: f32,
+    /// Smoothing of the second position smoother, which takes the edge off the first.
+    pub lag_smoothness: f32,
+    /// Smoothing of where the camera looks, in seconds.
+    pub look_smoothness: f32,
+    /// How far ahead of a moving target the camera tries to be, from 0 (trailing) to 1.
+    /// Only moves the camera; it always looks right at the target, see [`Self::look_lag`].
+    pub prediction: f32,
+}
+
+impl CameraTuning {
+    pub const SMOOTH: Self = Self {
+        position_smoothness: 1.0,
+        lag_smoothness: 2.5,
+        look_smoothness: 1.25,
+        prediction: 1.0,
+    };
+    pub const SNAPPY: Self = Self {
+        position_smoothness: 0.3,
+        lag_smoothness: 0.5,
+        look_smoothness: 0.3,
+        prediction: 0.5,
+    };
+    pub const OFF: Self = Self {
+        position_smoothness: 0.0,
+        lag_smoothness: 0.0,
+        look_smoothness: 0.0,
+        prediction: 0.0,
+    };
+
+    pub const PRESETS: [(&'static str, Self); 3] = [
+        ("Smooth", Self::SMOOTH),
+        ("Snappy", Self::SNAPPY),
+        ("Off", Self::OFF),
+    ];
+
+    const MAX_SMOOTHNESS: f32 = 5.0;
+
+    /// Dolly divides smoothness by this to get how fast a smoother closes the gap.
+    const DOLLY_SMOOTHNESS_MULT: f32 = 8.0;
+
+    /// How many seconds the look smoothing trails behind a steadily moving target.
+    /// Aiming this far ahead along the target's velocity keeps it centered.
+    pub fn look_lag(&self) -> f32 {
+        self.look_smoothness / Self::DOLLY_SMOOTHNESS_MULT
+    }
+
+    /// Keeps every field in a range that can't make the camera wobble uncontrollably.
+    pub fn clamped(self) -> Self {
+        Self {
+            position_smoothness: self.position_smoothness.clamp(0.0, Self::MAX_SMOOTHNESS),
+            lag_smoothness: self.lag_smoothness.clamp(0.0, Self::MAX_SMOOTHNESS),
+            look_smoothness: self.look_smoothness.clamp(0.0, Self::MAX_SMOOTHNESS),
+            prediction: self.prediction.clamp(0.0, 1.0),
+        }
+    }
+
+    /// The name of the preset these settings match, if any.
+    pub fn preset_name(&self) -> Option<&'static str> {
+        Self::PRESETS
+            .iter()
+            .find(|(_, preset)| preset == self)
+            .map(|(name, _)| *name)
+    }
+
+    /// Builds a camera rig at `position` looking at `target` with these settings.
+    pub fn build_rig(&self, position: glam::Vec3, target: glam::Vec3) -> Rig {
+        let offset_scale = prediction_offset_scale(self.prediction);
+
+        let mut rig = Rig::builder()
+            .with(Position::new(position))
+            .with(Smooth::new_position(self.position_smoothness))
+            .with(Smooth::new_position(self.lag_smoothness))
+            .with(LookAt::new(target).tracking_smoothness(self.look_smoothness))
+            .build();
+
+        rig.driver_mut::<Smooth>().output_offset_scale = offset_scale;
+
+        rig
+    }
+
+    /// Sliders and preset buttons for tuning the camera.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            for (name, preset) in Self::PRESETS {
+                if ui.selectable_label(*self == preset, name).clicked() {
+                    *self = preset;
+                }
+            }
+            if self.preset_name().is_none() {
+                ui.label("Custom");
+            }
+        });
+
+        let range = 0.0..=Self::MAX_SMOOTHNESS;
+        ui.add(egui::Slider::new(&mut self.position_smoothness, range.clone()).text("Smoothing"));
+        ui.add(egui::Slider::new(&mut self.lag_smoothness, range.clone()).text("Lag"));
+        ui.add(egui::Slider::new(&mut self.look_smoothness, range).text("Look smoothing"));
+        ui.add(egui::Slider::new(&mut self.prediction, 0.0..=1.0).text("Prediction"));
+    }
+}
+
+impl Default for CameraTuning {
+    fn default() -> Self {
+        Self::SMOOTH
+    }
+}
+
+/// Dolly's output offset scale for a prediction amount: 1 trails behind the target like a
+/// normal smoother would, -1 leads it by the same amount.
+fn prediction_offset_scale(prediction: f32) -> f32 {
+    1.0 - 2.0 * prediction
+}
+
+// rebuild the rig whenever the tuning changes, starting from wherever the camera is right now
+pub fn apply_camera_tuning(tuning: Res<CameraTuning>, mut rig: Query<&mut Rig>) {
+    // the rig spawned in `setup` is already built from the initial tuning
+    if !tuning.is_changed() || tuning.is_added() {
+        return;
+    }
+
+    let Ok(mut rig) = rig.get_single_mut() else {
+        debug!("no camera rig to apply the tuning to");
+        return;
+    };
+    let position = rig.final_transform.position;
+    let target = rig.driver::<LookAt>().target;
+
+    *rig = tuning.build_rig(position, target);
+}
+
+// predicting where the target is going is great for following a moving object, but when the
+// target jumps across the system (selecting a far away object) it overshoots and wobbles,
+// so fade the prediction out the further the camera has left to travel
+pub fn damp_camera_prediction(tuning: Res<CameraTuning>, mut rig: Query<&mut Rig>) {
+    let Ok(mut rig) = rig.get_single_mut() else {
+        debug!("no camera rig to damp");
+        return;
+    };
+
+    let position = rig.final_transform.position;
+    let destination = rig.driver::<Position>().position;
+    let target = rig.driver::<LookAt>().target;
+
+    // how far the camera has left to go, compared to how far it'll be from what it looks at
+    let offset = destination.distance(target).max(1.0);
+    let jump = position.distance(destination) / offset;
+    let damping = ((jump - 1.0) / 4.0).clamp(0.0, 1.0);
+
+    let offset_scale = prediction_offset_scale(tuning.prediction * (1.0 - damping));
+    rig.driver_mut::<Smooth>().output_offset_scale = offset_scale;
+}
+
+/// Where `point` shows up on screen, in egui's coordinates, or [`None`] if it's behind the camera.
+pub fn world_to_screen(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    point: Vec3,
+) -> Option<egui::Pos2> {
+    let viewport = camera.logical_viewport_size()?;
+    let position = camera.world_to_viewport(camera_transform, point)?;
+    // the viewport's origin is the bottom left, but egui's is the top left
+    Some(egui::pos2(position.x, viewport.y - position.y))
+}
+
+/// How far past the furthest object the far clipping plane is pushed, as a multiple of the
+/// distance it has to cover, so it isn't moved again every time something shifts a little.
+const FAR_PLANE_MARGIN: f32 = 1.5;
+
+// keep everything inside the camera's render range, however far out the data or the distance
+// exaggeration puts it
+pub fn fit_far_plane(
+    objs: Query<(&GlobalTransform, &DisplayRadius), With<SpaceObject>>,
+    mut cameras: Query<(&GlobalTransform, &mut Projection), With<Camera3d>>,
+) {
+    for (camera_transform, mut projection) in cameras.iter_mut() {
+        let Projection::Perspective(perspective) = projection.as_ref() else {
+            continue;
+        };
+        let camera = camera_transform.translation();
+        let needed = objs
+            .iter()
+            .map(|(transform, radius)| transform.translation().distance(camera) + radius.0)
+            // never closer than Bevy's own default
+            .fold(PerspectiveProjection::default().far, f32::max);
+
+        // only touch the projection when it's actually out of range, to not redo the frustum
+        if perspective.far < needed || perspective.far > needed * FAR_PLANE_MARGIN * 2.0 {
+            if let Projection::Perspective(perspective) = projection.as_mut() {
+                perspective.far = needed * FAR_PLANE_MARGIN;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        overview::DEFAULT_CAMERA_POSITION,
+        space::{BodyDatabase, ExaggerationSettings},
+    };
+
+    #[test]
+    fn far_plane_reaches_everything_from_the_default_view() {
+        let db = BodyDatabase::embedded();
+        let exaggeration = ExaggerationSettings::default();
+        let camera = Vec3::new(
+            DEFAULT_CAMERA_POSITION.x,
+            DEFAULT_CAMERA_POSITION.y,
+            DEFAULT_CAMERA_POSITION.z,
+        );
+
+        let mut app = App::new();
+        app.add_system(fit_far_plane);
+        app.world.spawn((
+            Camera3d::default(),
+            Projection::default(),
+            GlobalTransform::from_translation(camera),
+        ));
+        let mut furthest: f32 = 0.0;
+        for obj in enum_iterator::all::<SpaceObject>() {
+            let position = obj.start_position(&db, &exaggeration);
+            let radius = obj.scaled_radius(&db, &exaggeration);
+            furthest = furthest.max(position.distance(camera) + radius);
+            app.world.spawn((
+                obj,
+                GlobalTransform::from_translation(position),
+                DisplayRadius(radius),
+            ));
+        }
+
+        app.update();
+
+        let far = match app.world.query::<&Projection>().single(&app.world) {
+            Projection::Perspective(perspective) => perspective.far,
+            Projection::Orthographic(_) => unreachable!(),
+        };
+        assert!(furthest > PerspectiveProjection::default().far);
+        assert!(far >= furthest, "{far} doesn't reach {furthest}");
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    fs::File,
+    io::BufWriter,
+    num::NonZeroU32,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use bevy::{
+    app::AppExit,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_asset::RenderAssets,
+        render_resource::{
+            Buffer, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d,
+            ImageCopyBuffer, ImageDataLayout, MapMode, TextureDimension, TextureFormat,
+            TextureUsages,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        RenderApp, RenderStage,
+    },
+    window::WindowId,
+    winit::WinitWindows,
+};
+
+/// How many frames to let run before capturing, so textures load and the camera settles.
+const WARMUP_FRAMES: u32 = 120;
+
+/// Rows copied out of a texture have to be padded to a multiple of this many bytes.
+const COPY_BYTES_PER_ROW_ALIGNMENT: usize = 256;
+
+/// Render a single frame to a PNG and exit, asked for with `--render <file.png>`.
+///
+/// `--size <width>x<height>` picks the resolution (1280x720 by default), and `--state` takes a
+/// string from the share window to pick what to look at. A window is still made, since picking
+/// and the UI expect one, but it's hidden as soon as it's up and the camera draws into an image.
+#[derive(Debug, Clone)]
+pub struct CaptureRequest {
+    path: PathBuf,
+    width: u32,
+    height: u32,
+}
+
+impl CaptureRequest {
+    pub fn from_args() -> Option<Self> {
+        let mut args = std::env::args().skip(1);
+        let mut path = None;
+        let (mut width, mut height) = (1280, 720);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--render" => path = args.next().map(PathBuf::from),
+                "--size" => {
+                    let size = args.next().and_then(|size| {
+                        let (w, h) = size.split_once('x')?;
+                        Some((w.parse().ok()?, h.parse().ok()?))
+                    });
+                    match size {
+                        Some((w, h)) if w > 0 && h > 0 => (width, height) = (w, h),
+                        _ => warn!("--size should look like 1920x1080, using {width}x{height}"),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            path: path?,
+            width,
+            height,
+        })
+    }
+}
+
+pub struct CapturePlugin(pub CaptureRequest);
+
+impl Plugin for CapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.0.clone())
+            .add_plugin(ExtractResourcePlugin::<CaptureTarget>::default())
+            .add_startup_system_to_stage(StartupStage::PostStartup, setup_capture)
+            .add_system(hide_window)
+            .add_system(start_capture)
+            .add_system(exit_after_capture);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<CaptureReadback>()
+                // cleanup runs after the frame has been rendered and submitted
+                .add_system_to_stage(RenderStage::Cleanup, read_capture);
+        }
+    }
+}
+
+/// The image the camera draws into while capturing.
+#[derive(Resource, Clone, ExtractResource)]
+struct CaptureTarget {
+    image: Handle<Image>,
+    path: PathBuf,
+    /// Set once the warmup is over and the next frame should be saved.
+    ready: bool,
+    /// Set by the render world once the file is written.
+    done: Arc<AtomicBool>,
+}
+
+/// A copy of the frame on its way back from the GPU.
+#[derive(Resource, Default)]
+struct CaptureReadback(Option<Readback>);
+
+struct Readback {
+    buffer: Buffer,
+    /// Filled in once the GPU is done with the buffer.
+    mapped: Arc<Mutex<Option<Result<(), String>>>>,
+    width: u32,
+    height: u32,
+    padded_row: usize,
+}
+
+// point the camera at an image instead of the window
+fn setup_capture(
+    mut commands: Commands,
+    request: Res<CaptureRequest>,
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<&mut Camera, With<Camera3d>>,
+) {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: request.width,
+            height: request.height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC | TextureUsages::TEXTURE_BINDING;
+    let image = images.add(image);
+
+    for mut camera in cameras.iter_mut() {
+        camera.target = RenderTarget::Image(image.clone());
+    }
+
+    commands.insert_resource(CaptureTarget {
+        image,
+        path: request.path.clone(),
+        ready: false,
+        done: default(),
+    });
+}
+
+// the window can show up a frame late on some platforms, so keep looking until it's there
+fn hide_window(windows: NonSend<WinitWindows>, mut hidden: Local<bool>) {
+    if *hidden {
+        return;
+    }
+
+    if let Some(window) = windows.get_window(WindowId::primary()) {
+        window.set_visible(false);
+        *hidden = true;
+    }
+}
+
+fn start_capture(mut target: ResMut<CaptureTarget>, mut frames: Local<u32>) {
+    if target.ready {
+        return;
+    }
+
+    *frames += 1;
+    if *frames >= WARMUP_FRAMES {
+        target.ready = true;
+    }
+}
+
+fn exit_after_capture(target: Res<CaptureTarget>, mut exit: EventWriter<AppExit>) {
+    if target.done.load(Ordering::Acquire) {
+        exit.send(AppExit);
+    }
+}
+
+// copy the rendered frame into a buffer, then write it out once the GPU has handed it back
+fn read_capture(
+    target: Option<Res<CaptureTarget>>,
+    mut readback: ResMut<CaptureReadback>,
+    images: Res<RenderAssets<Image>>,
+    device: Res<RenderDevice>,
+    queue: Res<RenderQueue>,
+) {
+    let Some(target) = target else {
+        return;
+    };
+    if !target.ready || target.done.load(Ordering::Acquire) {
+        return;
+    }
+
+    if let Some(pending) = &readback.0 {
+        let mapped = pending.mapped.lock().unwrap().take();
+        match mapped {
+            Some(Ok(())) => save_capture(pending, &target),
+            Some(Err(err)) => {
+                error!("couldn't read the capture back: {err}");
+                target.done.store(true, Ordering::Release);
+            }
+            None => return,
+        }
+        readback.0 = None;
+        return;
+    }
+
+    let Some(image) = images.get(&target.image) else {
+        return;
+    };
+    let (width, height) = (image.size.x as u32, image.size.y as u32);
+    let unpadded_row = width as usize * 4;
+    let padded_row = (unpadded_row + COPY_BYTES_PER_ROW_ALIGNMENT - 1)
+        / COPY_BYTES_PER_ROW_ALIGNMENT
+        * COPY_BYTES_PER_ROW_ALIGNMENT;
+
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("capture buffer"),
+        size: (padded_row * height as usize) as u64,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("capture encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        image.texture.as_image_copy(),
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(padded_row as u32),
+                rows_per_image: None,
+            },
+        },
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit([encoder.finish()]);
+
+    // the buffer is mapped a frame or two later, whenever the device is next polled
+    let mapped = Arc::<Mutex<_>>::default();
+    let on_mapped = mapped.clone();
+    device.map_buffer(&buffer.slice(..), MapMode::Read, move |result| {
+        *on_mapped.lock().unwrap() = Some(result.map_err(|err| err.to_string()));
+    });
+
+    readback.0 = Some(Readback {
+        buffer,
+        mapped,
+        width,
+        height,
+        padded_row,
+    });
+}
+
+fn save_capture(readback: &Readback, target: &CaptureTarget) {
+    let pixels: Vec<u8> = {
+        let data = readback.buffer.slice(..).get_mapped_range();
+        data.chunks(readback.padded_row)
+            .flat_map(|row| &row[..readback.width as usize * 4])
+            .copied()
+            .collect()
+    };
+    readback.buffer.unmap();
+
+    let written = File::create(&target.path)
+        .map_err(|err| err.to_string())
+        .and_then(|file| {
+            let mut encoder =
+                png::Encoder::new(BufWriter::new(file), readback.width, readback.height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder
+                .write_header()
+                .and_then(|mut writer| writer.write_image_data(&pixels))
+                .map_err(|err| err.to_string())
+        });
+
+    match written {
+        Ok(()) => info!("saved a capture to {}", target.path.display()),
+        Err(err) => error!(
+            "couldn't save a capture to {}: {err}",
+            target.path.display()
+        ),
+    }
+    target.done.store(true, Ordering::Release);
+}
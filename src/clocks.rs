@@ -0,0 +1,93 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    camera::world_to_screen,
+    simulation::{simulated_date, OrbitState, SimulationClock},
+    space::{BodyDatabase, SpaceObject},
+    stats::ShowStatsStrip,
+};
+
+/// Whether each planet gets a little clock showing how far through its orbit it is.
+#[derive(Resource, Default)]
+pub struct ShowOrbitClocks(pub bool);
+
+/// The radius of a clock on screen, in points.
+const CLOCK_RADIUS: f32 = 10.0;
+
+/// How many line segments make up a full clock face.
+const CLOCK_SEGMENTS: usize = 32;
+
+// draw a ring next to each planet that fills up over one orbit
+pub fn orbit_clocks(
+    mut egui_ctx: ResMut<EguiContext>,
+    show: Res<ShowOrbitClocks>,
+    db: Res<BodyDatabase>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    objs: Query<(&SpaceObject, &OrbitState, &GlobalTransform)>,
+) {
+    if !show.0 {
+        return;
+    }
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+
+    let painter = egui_ctx
+        .ctx_mut()
+        .layer_painter(egui::LayerId::background());
+    let track = egui::Stroke::new(2.0, egui::Color32::from_gray(70));
+    let hand = egui::Stroke::new(2.0, egui::Color32::WHITE);
+
+    for (obj, state, transform) in objs.iter() {
+        if obj.orbits(&db) != SpaceObject::Sun || *obj == SpaceObject::Sun {
+            continue;
+        }
+        let Some(position) = world_to_screen(camera, camera_transform, transform.translation())
+        else {
+            continue;
+        };
+
+        let center = position + egui::vec2(CLOCK_RADIUS * 2.0, -CLOCK_RADIUS * 2.0);
+        let progress = state.progress(*obj, &db);
+
+        painter.circle_stroke(center, CLOCK_RADIUS, track);
+        let filled = (progress * CLOCK_SEGMENTS as f32).ceil() as usize;
+        let points = (0..=filled)
+            .map(|i| {
+                // clockwise from twelve o'clock
+                let angle = (i as f32 / CLOCK_SEGMENTS as f32).min(progress) * TAU;
+                center + CLOCK_RADIUS * egui::vec2(angle.sin(), -angle.cos())
+            })
+            .collect();
+        painter.add(egui::Shape::line(points, hand));
+        painter.text(
+            center + egui::vec2(CLOCK_RADIUS + 4.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            format!("{:.0}%", progress * 100.0),
+            egui::FontId::proportional(12.0),
+            egui::Color32::WHITE,
+        );
+    }
+}
+
+// the simulated date along the top of the screen, so stepping a day or a quarter orbit shows
+// how much time went by
+pub fn date_hud(
+    mut egui_ctx: ResMut<EguiContext>,
+    clock: Res<SimulationClock>,
+    db: Res<BodyDatabase>,
+    strip: Res<ShowStatsStrip>,
+) {
+    // the stats strip shows it instead
+    if strip.0 {
+        return;
+    }
+    egui::Area::new("simulated date")
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 8.0))
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.label(simulated_date(clock.shown_days(&db)));
+        });
+}
@@ -0,0 +1,55 @@
+use bevy::{prelude::*, transform::TransformSystem};
+
+use crate::{DisplayRadius, MainCamera, VisualExtent};
+
+/// Keeping the camera from flying through objects on its way somewhere.
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CollisionAvoidance>()
+            // after everything that moves the camera, but before the move is propagated
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                avoid_collisions.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Whether the camera is kept out of objects it would otherwise fly through.
+#[derive(Resource)]
+pub struct CollisionAvoidance(pub bool);
+
+impl Default for CollisionAvoidance {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// How far the camera is kept from an object's center, as a multiple of how far its visuals
+/// reach. Below [`CameraZoom::MIN`](crate::zoom::CameraZoom::MIN), so it never fights looking at something up close.
+const CAMERA_CLEARANCE: f32 = 1.1;
+
+// push the camera back out of anything it ends up inside, so passing an object on the way
+// somewhere slides around it instead of clipping through
+pub fn avoid_collisions(
+    avoidance: Res<CollisionAvoidance>,
+    objs: Query<(&Transform, &DisplayRadius, &VisualExtent), Without<MainCamera>>,
+    mut cam: Query<&mut Transform, With<MainCamera>>,
+) {
+    if !avoidance.0 {
+        return;
+    }
+    let Ok(mut cam) = cam.get_single_mut() else {
+        return;
+    };
+
+    for (transform, display_radius, extent) in objs.iter() {
+        let clearance = display_radius.0 * extent.0 * CAMERA_CLEARANCE;
+        let offset = cam.translation - transform.translation;
+        let distance = offset.length();
+        if distance < clearance && distance > 0.0 {
+            cam.translation = transform.translation + offset / distance * clearance;
+        }
+    }
+}
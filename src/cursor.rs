@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use bevy::{prelude::*, window::CursorIcon};
+use bevy_egui::{EguiContext, EguiSystem};
+use bevy_mod_picking::{HoverEvent, PickingEvent};
+
+/// A hand cursor over anything that can be clicked.
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        // egui sets the cursor while it processes its output, so this has to come after
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            hover_cursor.after(EguiSystem::ProcessOutput),
+        );
+    }
+}
+
+// show a hand over anything that can be clicked, leaving the cursor to egui over its windows
+fn hover_cursor(
+    mut events: EventReader<PickingEvent>,
+    mut hovered: Local<HashSet<Entity>>,
+    mut egui_ctx: ResMut<EguiContext>,
+    mut windows: ResMut<Windows>,
+) {
+    for event in events.iter() {
+        match event {
+            PickingEvent::Hover(HoverEvent::JustEntered(entity)) => {
+                hovered.insert(*entity);
+            }
+            PickingEvent::Hover(HoverEvent::JustLeft(entity)) => {
+                hovered.remove(entity);
+            }
+            _ => {}
+        }
+    }
+
+    if egui_ctx.ctx_mut().is_pointer_over_area() {
+        return;
+    }
+    let Some(window) = windows.get_primary_mut() else {
+        return;
+    };
+    let icon = if hovered.is_empty() {
+        CursorIcon::Default
+    } else {
+        CursorIcon::Hand
+    };
+    if window.cursor_icon() != icon {
+        window.set_cursor_icon(icon);
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    ladder::DistanceLadder,
+    scientific_notation,
+    space::{BodyDatabase, Category, SpaceObject},
+    with_separators,
+};
+
+// pick the body with the biggest `key`
+fn max_by(
+    objs: impl Iterator<Item = SpaceObject>,
+    key: impl Fn(SpaceObject) -> f32,
+) -> Option<SpaceObject> {
+    objs.max_by(|a, b| key(*a).total_cmp(&key(*b)))
+}
+
+// a window of stats about the whole system, worked out from the body data each frame
+// so it stays right when the data is reloaded
+pub fn facts_ui(
+    mut egui_ctx: ResMut<EguiContext>,
+    mut ladder: ResMut<DistanceLadder>,
+    db: Res<BodyDatabase>,
+) {
+    let all = || enum_iterator::all::<SpaceObject>();
+    let planets = || {
+        all().filter(|o| {
+            matches!(
+                o.category(),
+                Category::Terrestrial | Category::GasGiant | Category::IceGiant
+            )
+        })
+    };
+    let orbiting_sun =
+        || all().filter(|o| o.orbits(&db) == SpaceObject::Sun && *o != SpaceObject::Sun);
+
+    egui::Window::new("Solar system facts")
+        .default_open(false)
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.label(format!("Bodies: {}", all().count()));
+            ui.label(format!("Planets: {}", planets().count()));
+            ui.label(format!(
+                "Moons: {}",
+                all().filter(|o| o.category() == Category::Moon).count()
+            ));
+
+            let planet_mass: f32 = planets().map(|o| o.mass(&db)).sum();
+            ui.label(format!(
+                "Combined planet mass: {} kg ({:.3}% of the Sun)",
+                scientific_notation(planet_mass),
+                planet_mass / SpaceObject::Sun.mass(&db) * 100.0
+            ));
+
+            if let Some(closest) = max_by(orbiting_sun(), |o| -o.distance_from_sun(&db)) {
+                ui.label(format!(
+                    "Closest to the Sun: {} ({} AU)",
+                    closest.name(&db),
+                    closest.distance_from_sun(&db)
+                ));
+            }
+            if let Some(farthest) = max_by(orbiting_sun(), |o| o.distance_from_sun(&db)) {
+                ui.label(format!(
+                    "Farthest from the Sun: {} ({} AU)",
+                    farthest.name(&db),
+                    farthest.distance_from_sun(&db)
+                ));
+            }
+            if let Some(largest) = max_by(planets(), |o| o.radius(&db)) {
+                ui.label(format!(
+                    "Largest planet: {} ({} km across)",
+                    largest.name(&db),
+                    with_separators(largest.radius(&db) * 2.0)
+                ));
+            }
+            if let Some(largest) = max_by(
+                all().filter(|o| o.category() == Category::Moon),
+                |o| o.radius(&db),
+            ) {
+                ui.label(format!(
+                    "Largest moon: {} ({} km across, orbits {})",
+                    largest.name(&db),
+                    with_separators(largest.radius(&db) * 2.0),
+                    largest.orbits(&db).name(&db)
+                ));
+            }
+            if let Some(hottest) = max_by(orbiting_sun(), |o| o.temperature(&db)) {
+                ui.label(format!(
+                    "Hottest planet: {} ({}°C)",
+                    hottest.name(&db),
+                    hottest.temperature(&db)
+                ));
+            }
+            if let Some(most_moons) = max_by(orbiting_sun(), |o| o.number_of_known_moons(&db) as f32) {
+                ui.label(format!(
+                    "Most known moons: {} ({})",
+                    most_moons.name(&db),
+                    most_moons.number_of_known_moons(&db)
+                ));
+            }
+
+            ui.separator();
+            if ui
+                .add_enabled(!ladder.is_running(), egui::Button::new("Distance ladder"))
+                .on_hover_text("Zooms out from Earth past every planet's orbit, to show how far apart they are")
+                .clicked()
+            {
+                ladder.start();
+            }
+        });
+}
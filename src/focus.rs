@@ -0,0 +1,62 @@
+use bevy::{
+    prelude::*,
+    window::WindowFocused,
+    winit::{UpdateMode, WinitSettings},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{Persisted, PersistedAppExt};
+
+/// Keeps track of whether the window is focused, and slows redrawing in the background when the
+/// simulation pauses there.
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WindowFocus>()
+            .init_persisted_resource::<PauseWhenUnfocused>()
+            .add_system(track_window_focus)
+            .add_system(apply_pause_when_unfocused);
+    }
+}
+
+/// Whether the window has focus, so the simulation can stop while it's in the background.
+#[derive(Resource)]
+pub struct WindowFocus(pub bool);
+
+impl Default for WindowFocus {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Whether to stop the simulation and redraw less while the window isn't focused.
+/// Off by default so kiosks showing the app unattended keep it moving.
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct PauseWhenUnfocused(pub bool);
+
+impl Persisted for PauseWhenUnfocused {
+    const KEY: &'static str = "pause_when_unfocused";
+}
+
+pub fn track_window_focus(mut events: EventReader<WindowFocused>, mut focus: ResMut<WindowFocus>) {
+    for event in events.iter() {
+        focus.0 = event.focused;
+    }
+}
+
+// with the simulation stopped in the background there's nothing to redraw every frame for
+pub fn apply_pause_when_unfocused(
+    pause_when_unfocused: Res<PauseWhenUnfocused>,
+    mut winit: ResMut<WinitSettings>,
+) {
+    if pause_when_unfocused.is_changed() {
+        winit.unfocused_mode = if pause_when_unfocused.0 {
+            UpdateMode::ReactiveLowPower {
+                max_wait: std::time::Duration::from_secs(1),
+            }
+        } else {
+            UpdateMode::Continuous
+        };
+    }
+}
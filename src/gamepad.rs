@@ -0,0 +1,82 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    overview::CameraPan,
+    selection::{cycle_object, object_selected, reset_view, CurrentObject, Selection},
+    space::SpaceObject,
+    CameraOrbit,
+};
+
+/// Looking around and stepping through the objects with a controller.
+pub struct GamepadPlugin;
+
+impl Plugin for GamepadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(gamepad_input.after(object_selected));
+    }
+}
+
+/// Radians per second the camera orbits at with the left stick fully deflected.
+const ORBIT_SPEED: f32 = 1.5;
+
+/// How far a stick has to be pushed before it counts as a press.
+const STICK_THRESHOLD: f32 = 0.5;
+
+// left stick orbits the camera, the right stick or bumpers cycle the selected object, and
+// the east button (B on Xbox, circle on PlayStation) resets the view, just like escape
+#[allow(clippy::too_many_arguments)]
+pub fn gamepad_input(
+    mut selection: Selection,
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    buttons: Res<Input<GamepadButton>>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+    mut orbit: ResMut<CameraOrbit>,
+    mut pan: ResMut<CameraPan>,
+    mut sticks_held: Local<HashSet<Gamepad>>,
+) {
+    for gamepad in gamepads.iter() {
+        let axis = |axis_type| {
+            axes.get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or(0.0)
+        };
+        let pressed = |button_type| buttons.just_pressed(GamepadButton::new(gamepad, button_type));
+
+        let delta = ORBIT_SPEED * time.delta_seconds();
+        orbit.yaw -= axis(GamepadAxisType::LeftStickX) * delta;
+        orbit.pitch = (orbit.pitch + axis(GamepadAxisType::LeftStickY) * delta)
+            .clamp(-89.0_f32.to_radians(), 89.0_f32.to_radians());
+
+        // only cycle once per flick of the stick, not every frame it's held
+        let stick = axis(GamepadAxisType::RightStickX);
+        let flicked = if stick.abs() < STICK_THRESHOLD {
+            sticks_held.remove(&gamepad);
+            None
+        } else if sticks_held.insert(gamepad) {
+            Some(stick > 0.0)
+        } else {
+            None
+        };
+
+        let forward = if pressed(GamepadButtonType::RightTrigger) {
+            Some(true)
+        } else if pressed(GamepadButtonType::LeftTrigger) {
+            Some(false)
+        } else {
+            flicked
+        };
+
+        if let Some(forward) = forward {
+            let next = cycle_object(current.get_single().ok().copied(), forward);
+            selection.select(next);
+        }
+
+        if pressed(GamepadButtonType::East) {
+            info!("Gamepad reset pressed");
+
+            reset_view(&mut selection, &mut pan);
+            *orbit = CameraOrbit::default();
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::{pbr::NotShadowCaster, prelude::*};
+
+use crate::{
+    rings::ring_mesh,
+    space::{ExaggerationSettings, SpaceObject},
+};
+
+/// Whether the band around the Sun where liquid water could exist on a planet's surface is shown.
+#[derive(Resource, Default)]
+pub struct ShowHabitableZone(pub bool);
+
+/// Marks the mesh drawing the habitable zone.
+#[derive(Component)]
+pub struct HabitableZone;
+
+/// Where the habitable zone starts and ends, in AU. Estimates vary, these are conservative ones.
+const INNER_EDGE: f32 = 0.95;
+const OUTER_EDGE: f32 = 1.37;
+
+// lay a translucent band in the ecliptic, redrawn whenever it's toggled or the scale changes
+pub fn draw_habitable_zone(
+    mut commands: Commands,
+    show: Res<ShowHabitableZone>,
+    exaggeration: Res<ExaggerationSettings>,
+    zones: Query<Entity, With<HabitableZone>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !show.is_changed() && !exaggeration.is_changed() {
+        return;
+    }
+
+    for entity in zones.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if !show.0 {
+        return;
+    }
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(ring_mesh(
+                SpaceObject::scaled_au(INNER_EDGE, &exaggeration),
+                SpaceObject::scaled_au(OUTER_EDGE, &exaggeration),
+                256,
+            )),
+            material: materials.add(StandardMaterial {
+                base_color: Color::rgba(0.2, 0.9, 0.3, 0.15),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                double_sided: true,
+                cull_mode: None,
+                ..default()
+            }),
+            ..default()
+        },
+        NotShadowCaster,
+        HabitableZone,
+    ));
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimePlugin};
+use bevy_mod_picking::PickingEvent;
+
+use crate::{
+    focus::{PauseWhenUnfocused, WindowFocus},
+    overview::CameraPan,
+    simulation::{OrbitState, SimulationPlugin},
+    space::{BodyDatabase, ExaggerationSettings, SpaceObject},
+    AppState,
+};
+
+/// An app for tests, with every object in it and the simulation running, but no window,
+/// rendering or UI. Time only moves on through [`advance`], so runs come out the same every time.
+pub fn app() -> App {
+    let db = BodyDatabase::embedded();
+    let exaggeration = ExaggerationSettings::default();
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins.build().disable::<TimePlugin>())
+        .init_resource::<Time>()
+        .init_resource::<Input<KeyCode>>()
+        .init_resource::<CameraPan>()
+        .init_resource::<WindowFocus>()
+        .init_resource::<PauseWhenUnfocused>()
+        .add_event::<PickingEvent>()
+        .add_state(AppState::Running)
+        .add_plugin(SimulationPlugin);
+
+    for obj in enum_iterator::all::<SpaceObject>() {
+        let start = obj.start_position(&db, &exaggeration);
+        app.world.spawn((
+            obj,
+            OrbitState::at(start),
+            TransformBundle::from_transform(Transform::from_translation(start)),
+        ));
+    }
+    app.insert_resource(db).insert_resource(exaggeration);
+
+    // start the clock, so the first frame is as long as the rest
+    app.world.resource_mut::<Time>().update();
+    app
+}
+
+/// Runs `frames` frames of `frame_time` seconds each.
+pub fn advance(app: &mut App, frames: u32, frame_time: f32) {
+    for _ in 0..frames {
+        let mut time = app.world.resource_mut::<Time>();
+        let last = time.last_update().unwrap_or_else(|| time.startup());
+        time.update_with_instant(last + Duration::from_secs_f32(frame_time));
+        app.update();
+    }
+}
+
+/// The entity for `obj`.
+pub fn entity(app: &mut App, obj: SpaceObject) -> Entity {
+    app.world
+        .query::<(Entity, &SpaceObject)>()
+        .iter(&app.world)
+        .find(|(_, other)| **other == obj)
+        .map(|(entity, _)| entity)
+        .unwrap_or_else(|| panic!("no entity for {obj:?}"))
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use crate::{
+    palette::MoonPalette,
+    space::{BodyDatabase, SpaceObject},
+    ObjectMaterials,
+};
+
+/// How brightly each object is drawn.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Illumination {
+    /// Everything as bright as its colors, so the outer system is as easy to see as the inner.
+    #[default]
+    Uniform,
+    /// Dimmed by how much sunlight reaches it and how much it reflects, compared to Earth, so the
+    /// outer system really is dark.
+    Sunlight,
+}
+
+/// The brightest an object is drawn in [`Illumination::Sunlight`], compared to Earth, so Mercury
+/// and Venus aren't blown out to white.
+const MAX_BRIGHTNESS: f32 = 2.0;
+
+// how bright sunlight makes `obj` look compared to Earth: it falls off with the square of the
+// distance, then the surface reflects some fraction of it
+fn sunlit_brightness(obj: SpaceObject, db: &BodyDatabase) -> f32 {
+    let distance = obj.distance_from_sun(db) / SpaceObject::Earth.distance_from_sun(db);
+    let reflected = obj.albedo(db) / SpaceObject::Earth.albedo(db);
+    (reflected / (distance * distance)).min(MAX_BRIGHTNESS)
+}
+
+// redo every object's color when the lighting or the palette it starts from changes
+pub fn apply_illumination(
+    illumination: Res<Illumination>,
+    palette: Res<MoonPalette>,
+    db: Res<BodyDatabase>,
+    objs: Query<(&SpaceObject, &ObjectMaterials)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !illumination.is_changed() && !palette.is_changed() || illumination.is_added() {
+        return;
+    }
+
+    for (obj, obj_materials) in objs.iter() {
+        let color = palette.color(*obj).unwrap_or(Color::WHITE);
+        let Some(material) = materials.get_mut(&obj_materials.textured) else {
+            continue;
+        };
+        material.base_color = match *illumination {
+            Illumination::Uniform => color,
+            Illumination::Sunlight => {
+                // scale the light itself, which is linear, rather than the sRGB color
+                let [r, g, b, a] = color.as_linear_rgba_f32();
+                let brightness = sunlit_brightness(*obj, &db);
+                Color::rgba_linear(r * brightness, g * brightness, b * brightness, a)
+            }
+        };
+    }
+}
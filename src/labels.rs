@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    camera::world_to_screen,
+    settings::Persisted,
+    space::{BodyDatabase, SpaceObject},
+    DisplayRadius,
+};
+
+/// Whether every object has its name floating above it.
+#[derive(Resource, Serialize, Deserialize)]
+pub struct ShowLabels(pub bool);
+
+impl Default for ShowLabels {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+impl Persisted for ShowLabels {
+    const KEY: &'static str = "show_labels";
+}
+
+/// How big the names are, in points. They're drawn over the scene, so they're just as readable
+/// however far away the object is.
+const LABEL_SIZE: f32 = 13.0;
+
+/// How far apart on screen a moon and its planet are, in points, before the moon's label starts
+/// fading in and when it's fully shown. Zoomed out, the moons' names would all pile up on their
+/// planet's.
+const MOON_FADE_START: f32 = 20.0;
+const MOON_FADE_END: f32 = 60.0;
+
+// write each object's name just above it, facing the camera since it's drawn flat on the screen
+pub fn object_labels(
+    mut egui_ctx: ResMut<EguiContext>,
+    show: Res<ShowLabels>,
+    db: Res<BodyDatabase>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    objs: Query<(
+        &SpaceObject,
+        &GlobalTransform,
+        &DisplayRadius,
+        &ComputedVisibility,
+    )>,
+) {
+    if !show.0 {
+        return;
+    }
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+
+    let centers: HashMap<SpaceObject, egui::Pos2> = objs
+        .iter()
+        .filter_map(|(obj, transform, _, _)| {
+            let center = world_to_screen(camera, camera_transform, transform.translation())?;
+            Some((*obj, center))
+        })
+        .collect();
+
+    let painter = egui_ctx
+        .ctx_mut()
+        .layer_painter(egui::LayerId::background());
+
+    for (obj, transform, radius, visibility) in objs.iter() {
+        // hidden by the spotlight, or off screen
+        if !visibility.is_visible() {
+            continue;
+        }
+        let Some(center) = centers.get(obj) else {
+            continue;
+        };
+
+        let opacity = if obj.orbits(&db) == SpaceObject::Sun {
+            1.0
+        } else {
+            let separation = centers
+                .get(&obj.orbits(&db))
+                .map_or(f32::INFINITY, |parent| parent.distance(*center));
+            ((separation - MOON_FADE_START) / (MOON_FADE_END - MOON_FADE_START)).clamp(0.0, 1.0)
+        };
+        if opacity <= 0.0 {
+            continue;
+        }
+
+        // just clear of the top of the object, however big it's drawn
+        let top = transform.translation() + camera_transform.up() * radius.0;
+        let anchor = world_to_screen(camera, camera_transform, top)
+            .map_or(*center, |top| egui::pos2(center.x, top.y.min(center.y)));
+
+        painter.text(
+            anchor - egui::vec2(0.0, 4.0),
+            egui::Align2::CENTER_BOTTOM,
+            obj.name(&db),
+            egui::FontId::proportional(LABEL_SIZE),
+            egui::Color32::from_white_alpha((opacity * 255.0) as u8),
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::{
+    input::{gamepad::GamepadEvent, mouse::MouseWheel},
+    prelude::*,
+};
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    selection::Selection,
+    space::{BodyDatabase, ExaggerationSettings, SpaceObject},
+    with_separators, DisplayRadius, MainCamera,
+};
+
+/// How long the camera stays at each step of the ladder, in seconds.
+const HOLD_DURATION: f32 = 3.0;
+
+/// How long the camera takes to move between steps, in seconds.
+const TRAVEL_DURATION: f32 = 4.0;
+
+/// How far the camera backs off from an orbit, as a multiple of its radius, so it fits on screen.
+const ORBIT_FRAMING: f32 = 2.5;
+
+/// How far the camera starts from Earth, as a multiple of its radius.
+const EARTH_FRAMING: f32 = 6.0;
+
+/// How many minutes light takes to cross one AU.
+const LIGHT_MINUTES_PER_AU: f32 = 8.317;
+
+/// The "distance ladder": a scripted zoom out from Earth, stopping at each orbit further out.
+#[derive(Resource, Default)]
+pub struct DistanceLadder {
+    /// How far into it the camera is, in seconds, if it's running.
+    elapsed: Option<f32>,
+    caption: String,
+}
+
+impl DistanceLadder {
+    pub fn start(&mut self) {
+        self.elapsed = Some(0.0);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.elapsed.is_some()
+    }
+}
+
+/// Somewhere the camera stops along the way.
+struct Step {
+    target: Vec3,
+    distance: f32,
+    caption: String,
+}
+
+fn light_time(au: f32) -> String {
+    let minutes = au * LIGHT_MINUTES_PER_AU;
+    if minutes < 60.0 {
+        format!("{minutes:.0} minutes")
+    } else {
+        format!("{:.1} hours", minutes / 60.0)
+    }
+}
+
+// Earth up close, then the orbit of every planet from Earth's outwards
+fn steps(
+    objs: &Query<(&SpaceObject, &Transform, &DisplayRadius), Without<MainCamera>>,
+    db: &BodyDatabase,
+    exaggeration: &ExaggerationSettings,
+) -> Vec<Step> {
+    let mut steps = Vec::new();
+    if let Some((_, transform, radius)) = objs.iter().find(|(obj, ..)| **obj == SpaceObject::Earth)
+    {
+        steps.push(Step {
+            target: transform.translation,
+            distance: radius.0 * EARTH_FRAMING,
+            caption: format!(
+                "Earth is {} km across.",
+                with_separators(SpaceObject::Earth.radius(db) * 2.0)
+            ),
+        });
+    }
+
+    let planets = SpaceObject::all_in_orbit_order(db)
+        .into_iter()
+        .filter(|obj| {
+            obj.orbits(db) == SpaceObject::Sun
+                && *obj != SpaceObject::Sun
+                && obj.distance_from_parent(db) >= SpaceObject::Earth.distance_from_parent(db)
+        });
+    steps.extend(planets.map(|obj| Step {
+        target: Vec3::ZERO,
+        distance: obj.display_distance(db, exaggeration) * ORBIT_FRAMING,
+        caption: format!(
+            "{} is {} AU from the Sun. Its light takes {} to reach us.",
+            obj.name(db),
+            with_separators(obj.distance_from_parent(db)),
+            light_time(obj.distance_from_parent(db)),
+        ),
+    }));
+    steps
+}
+
+// move the camera along the ladder, stopping it as soon as there's any input
+#[allow(clippy::too_many_arguments)]
+pub fn distance_ladder(
+    mut selection: Selection,
+    time: Res<Time>,
+    kbd: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    mut wheel: EventReader<MouseWheel>,
+    mut gamepad: EventReader<GamepadEvent>,
+    touches: Res<Touches>,
+    mut ladder: ResMut<DistanceLadder>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    objs: Query<(&SpaceObject, &Transform, &DisplayRadius), Without<MainCamera>>,
+    mut cam: Query<&mut Transform, (With<MainCamera>, Without<SpaceObject>)>,
+) {
+    let interrupted = wheel.iter().count() + gamepad.iter().count() > 0
+        || kbd.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || touches.iter_just_pressed().next().is_some();
+
+    let Some(elapsed) = ladder.elapsed else {
+        return;
+    };
+    let steps = steps(&objs, &db, &exaggeration);
+    let duration =
+        steps.len() as f32 * HOLD_DURATION + steps.len().saturating_sub(1) as f32 * TRAVEL_DURATION;
+    if interrupted || elapsed > duration || steps.is_empty() {
+        ladder.elapsed = None;
+        return;
+    }
+
+    // nothing else moves the camera while the ladder has it
+    selection.deselect();
+
+    let index = ((elapsed / (HOLD_DURATION + TRAVEL_DURATION)) as usize).min(steps.len() - 1);
+    let into_travel = elapsed - index as f32 * (HOLD_DURATION + TRAVEL_DURATION) - HOLD_DURATION;
+    let from = &steps[index];
+    let to = steps.get(index + 1).unwrap_or(from);
+    let t = (into_travel / TRAVEL_DURATION).clamp(0.0, 1.0);
+    let t = t * t * (3.0 - 2.0 * t);
+
+    // the steps grow by orders of magnitude, so move through them at an even pace on a log scale
+    let distance = (from.distance.ln() + (to.distance.ln() - from.distance.ln()) * t).exp();
+    let target = from.target.lerp(to.target, t);
+    let position = target + Vec3::new(0.0, 0.5, 1.0).normalize() * distance;
+
+    if let Ok(mut cam) = cam.get_single_mut() {
+        *cam = Transform::from_translation(position).looking_at(target, Vec3::Y);
+    }
+
+    ladder.caption = if t < 0.5 { from } else { to }.caption.clone();
+    ladder.elapsed = Some(elapsed + time.delta_seconds());
+}
+
+pub fn ladder_caption(mut egui_ctx: ResMut<EguiContext>, ladder: Res<DistanceLadder>) {
+    if !ladder.is_running() {
+        return;
+    }
+
+    egui::Area::new("distance ladder")
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -48.0))
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.label(
+                egui::RichText::new(&ladder.caption)
+                    .size(20.0)
+                    .color(egui::Color32::WHITE),
+            );
+            ui.label("Press any key to stop.");
+        });
+}
@@ -0,0 +1,264 @@
+use bevy::{core_pipeline::tonemapping::Tonemapping, prelude::*};
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    asteroids::ShowAsteroidBelt, axes::ShowAxes, collision::CollisionAvoidance,
+    habitable::ShowHabitableZone, illumination::Illumination, orbits::OrbitDisplay,
+    space::ExaggerationSettings, spotlight::Spotlight, sun::DimSunNearby,
+};
+
+/// A dim directional light that softens the pitch-black night sides of everything,
+/// for a more photographic look than turning up the ambient light. Off by default,
+/// since in space the far side really is dark.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct FillLight {
+    pub enabled: bool,
+    /// Brightness in lux.
+    pub illuminance: f32,
+    /// Which way the light points, around the vertical axis, in degrees.
+    pub yaw: f32,
+    /// How far the light tilts down from horizontal, in degrees.
+    pub pitch: f32,
+}
+
+impl Default for FillLight {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            illuminance: 5_000.0,
+            yaw: 30.0,
+            pitch: 20.0,
+        }
+    }
+}
+
+impl FillLight {
+    const MAX_ILLUMINANCE: f32 = 30_000.0;
+
+    fn transform(&self) -> Transform {
+        Transform::from_rotation(Quat::from_euler(
+            EulerRot::YXZ,
+            self.yaw.to_radians(),
+            -self.pitch.to_radians(),
+            0.0,
+        ))
+    }
+}
+
+/// Marks the entity holding the [`FillLight`].
+#[derive(Component)]
+pub struct FillLightSource;
+
+pub fn spawn_fill_light(mut commands: Commands, fill: Res<FillLight>) {
+    commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                illuminance: 0.0,
+                shadows_enabled: false,
+                ..default()
+            },
+            transform: fill.transform(),
+            ..default()
+        },
+        FillLightSource,
+    ));
+}
+
+pub fn apply_fill_light(
+    fill: Res<FillLight>,
+    mut lights: Query<(&mut DirectionalLight, &mut Transform), With<FillLightSource>>,
+) {
+    if !fill.is_changed() {
+        return;
+    }
+
+    for (mut light, mut transform) in lights.iter_mut() {
+        light.illuminance = if fill.enabled { fill.illuminance } else { 0.0 };
+        *transform = fill.transform();
+    }
+}
+
+/// How the camera maps the scene's brightness onto the screen. Turning the tonemapping off
+/// makes bright areas clip sooner, but keeps colors punchier on dim displays.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToneMapping {
+    pub enabled: bool,
+    /// Adds a little noise to hide banding in smooth gradients, like the Sun's glow.
+    pub deband_dither: bool,
+}
+
+impl Default for ToneMapping {
+    // what `Camera3dBundle` starts out with
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            deband_dither: true,
+        }
+    }
+}
+
+pub fn apply_tone_mapping(
+    tone_mapping: Res<ToneMapping>,
+    mut cameras: Query<&mut Tonemapping, With<Camera3d>>,
+) {
+    if !tone_mapping.is_changed() {
+        return;
+    }
+
+    for mut tonemapping in cameras.iter_mut() {
+        *tonemapping = if tone_mapping.enabled {
+            Tonemapping::Enabled {
+                deband_dither: tone_mapping.deband_dither,
+            }
+        } else {
+            Tonemapping::Disabled
+        };
+    }
+}
+
+// a window for the look of the scene, as opposed to what's in it
+#[allow(clippy::too_many_arguments)]
+pub fn effects_ui(
+    mut egui_ctx: ResMut<EguiContext>,
+    mut fill: ResMut<FillLight>,
+    mut exaggeration: ResMut<ExaggerationSettings>,
+    mut orbits: ResMut<OrbitDisplay>,
+    mut axes: ResMut<ShowAxes>,
+    mut habitable_zone: ResMut<ShowHabitableZone>,
+    mut asteroid_belt: ResMut<ShowAsteroidBelt>,
+    mut spotlight: ResMut<Spotlight>,
+    mut dim_sun: ResMut<DimSunNearby>,
+    mut illumination: ResMut<Illumination>,
+    mut tone_mapping: ResMut<ToneMapping>,
+    mut avoidance: ResMut<CollisionAvoidance>,
+) {
+    egui::Window::new("Effects")
+        .default_open(false)
+        .show(egui_ctx.ctx_mut(), |ui| {
+            // edit a copy so the light is only touched when something actually changes
+            let mut edited = *fill;
+            ui.checkbox(&mut edited.enabled, "Fill light")
+                .on_hover_text("Lights up the dark sides a little, for a softer look");
+            ui.add_enabled_ui(edited.enabled, |ui| {
+                ui.add(
+                    egui::Slider::new(&mut edited.illuminance, 0.0..=FillLight::MAX_ILLUMINANCE)
+                        .text("Brightness"),
+                );
+                ui.add(egui::Slider::new(&mut edited.yaw, -180.0..=180.0).text("Direction"));
+                ui.add(egui::Slider::new(&mut edited.pitch, -90.0..=90.0).text("Height"));
+            });
+            if edited != *fill {
+                *fill = edited;
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Object brightness:");
+                let mut edited = *illumination;
+                ui.radio_value(&mut edited, Illumination::Uniform, "Uniform")
+                    .on_hover_text("Everything is easy to see, however far it is from the Sun");
+                ui.radio_value(&mut edited, Illumination::Sunlight, "By sunlight")
+                    .on_hover_text("Dims each object by how much sunlight reaches it and how much it reflects");
+                if edited != *illumination {
+                    *illumination = edited;
+                }
+            });
+
+            ui.separator();
+            let mut edited = *tone_mapping;
+            ui.checkbox(&mut edited.enabled, "Tonemapping").on_hover_text(
+                "Softens the brightest parts of the scene. Try turning it off if everything looks washed out",
+            );
+            ui.add_enabled_ui(edited.enabled, |ui| {
+                ui.checkbox(&mut edited.deband_dither, "Smooth gradients")
+                    .on_hover_text("Hides banding in the Sun's glow and other soft gradients");
+            });
+            if edited != *tone_mapping {
+                *tone_mapping = edited;
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Orbits:");
+                let mut edited = *orbits;
+                ui.radio_value(&mut edited, OrbitDisplay::Hidden, "Hidden");
+                ui.radio_value(&mut edited, OrbitDisplay::Lines, "Lines");
+                ui.radio_value(&mut edited, OrbitDisplay::Tubes, "Tubes")
+                    .on_hover_text("Easier to see from far away and on projectors");
+                if edited != *orbits {
+                    *orbits = edited;
+                }
+            });
+            let mut show_axes = axes.0;
+            if ui
+                .checkbox(&mut show_axes, "Rotation axis")
+                .on_hover_text(
+                    "Draws a line through the selected object's poles, to see how far it's tilted",
+                )
+                .changed()
+            {
+                axes.0 = show_axes;
+            }
+            let mut show_zone = habitable_zone.0;
+            if ui
+                .checkbox(&mut show_zone, "Habitable zone")
+                .on_hover_text("Shades the band around the Sun where a planet could have liquid water")
+                .changed()
+            {
+                habitable_zone.0 = show_zone;
+            }
+            let mut show_belt = asteroid_belt.0;
+            if ui
+                .checkbox(&mut show_belt, "Asteroid belt")
+                .on_hover_text("Scatters rocks between Mars and Jupiter. Turn it off if things get slow")
+                .changed()
+            {
+                asteroid_belt.0 = show_belt;
+            }
+            let mut spotlit = spotlight.0;
+            if ui
+                .checkbox(&mut spotlit, "Spotlight the selected object")
+                .on_hover_text("Hides everything but the selected object and its neighbours, for presenting one world")
+                .changed()
+            {
+                spotlight.0 = spotlit;
+            }
+            let mut dimmed = dim_sun.0;
+            if ui
+                .checkbox(&mut dimmed, "Dim the Sun for the inner planets")
+                .on_hover_text("Turns the Sun down while looking at the inner planets, so its glare doesn't wash them out")
+                .changed()
+            {
+                dim_sun.0 = dimmed;
+            }
+            let mut avoiding = avoidance.0;
+            if ui
+                .checkbox(&mut avoiding, "Keep the camera out of objects")
+                .on_hover_text("Steers around anything in the way instead of flying through it")
+                .changed()
+            {
+                avoidance.0 = avoiding;
+            }
+
+            ui.separator();
+            let mut edited = *exaggeration;
+            ui.horizontal(|ui| {
+                ui.label("Scale:");
+                ui.selectable_value(&mut edited, ExaggerationSettings::DIAGRAM, "Diagram");
+                ui.selectable_value(&mut edited, ExaggerationSettings::REALISTIC, "Realistic");
+            });
+            ui.add(
+                egui::Slider::new(&mut edited.size, 1.0..=1_000.0)
+                    .logarithmic(true)
+                    .text("Size exaggeration"),
+            );
+            ui.add(
+                egui::Slider::new(&mut edited.distance, 0.1..=10.0)
+                    .logarithmic(true)
+                    .text("Distance exaggeration"),
+            );
+            if edited != *exaggeration {
+                *exaggeration = edited;
+            }
+        });
+}
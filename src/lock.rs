@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_dolly::{dolly::glam, prelude::*};
+
+use crate::{
+    apply_display_size,
+    camera::CameraTuning,
+    selection::{escape, object_selected, CurrentObject},
+    simulation::interpolate_orbits,
+    space::{BodyDatabase, SpaceObject},
+    zoom::{keyboard_zoom, pinch_zoom, scroll_zoom, CameraZoom},
+    CameraOrbit, DisplayRadius, VisualExtent,
+};
+
+/// The camera following the selected object around, from its usual angle, from above, or riding
+/// along behind it.
+pub struct LockPlugin;
+
+impl Plugin for LockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RideAlong>()
+            .init_resource::<TopDownView>()
+            .add_system(track_velocity.after(interpolate_orbits))
+            .add_system(
+                lock_to_object
+                    .after(object_selected)
+                    .after(apply_display_size)
+                    .after(track_velocity)
+                    .after(keyboard_zoom)
+                    .after(pinch_zoom)
+                    .after(scroll_zoom),
+            )
+            .add_system(toggle_top_down.after(escape).before(lock_to_object));
+    }
+}
+
+/// How fast an object is moving through the scene, in scene units per second.
+#[derive(Component, Default)]
+pub struct Velocity {
+    pub linear: Vec3,
+    last_translation: Option<Vec3>,
+}
+
+/// The object the camera travels alongside instead of sitting at a fixed offset from.
+/// Only has an effect while that object is selected, so it's per selection.
+#[derive(Resource, Default)]
+pub struct RideAlong(pub Option<SpaceObject>);
+
+/// The object the camera looks straight down on from above, instead of its usual angle.
+/// Only has an effect while that object is selected, and is dropped once it isn't.
+#[derive(Resource, Default)]
+pub struct TopDownView(pub Option<SpaceObject>);
+
+/// How far off vertical the view from above is, as a fraction of the camera's distance.
+const TOP_DOWN_OFFSET: f32 = 1e-3;
+
+// keep the camera on the selected object, framed by how big it's drawn, following it as it moves
+#[allow(clippy::too_many_arguments)]
+pub fn lock_to_object(
+    planet: Query<
+        (
+            &SpaceObject,
+            &Transform,
+            &Velocity,
+            &DisplayRadius,
+            &VisualExtent,
+        ),
+        With<CurrentObject>,
+    >,
+    mut rig: Query<&mut Rig>,
+    orbit: Res<CameraOrbit>,
+    zoom: Res<CameraZoom>,
+    ride_along: Res<RideAlong>,
+    top_down: Res<TopDownView>,
+    tuning: Res<CameraTuning>,
+    db: Res<BodyDatabase>,
+) {
+    if let Ok((planet, transform, velocity, display_radius, extent)) = planet.get_single() {
+        let Ok(mut rig) = rig.get_single_mut() else {
+            debug!("no camera rig to lock to {}", planet.name(&db));
+            return;
+        };
+        // aim where the object will be once the look smoothing catches up, so it stays centered
+        let lead = velocity.linear * tuning.look_lag();
+        rig.driver_mut::<LookAt>().target =
+            transform.transform_2_dolly().position + glam::Vec3::new(lead.x, lead.y, lead.z);
+        let distance = display_radius.0 * extent.0 * zoom.0;
+
+        let looking_down = top_down.0 == Some(*planet);
+        let mut cam_pos = if looking_down {
+            // a sliver off straight up, since looking exactly along the up axis leaves no way to
+            // tell which way up the picture is
+            glam::Vec3::Y * distance + glam::Vec3::Z * distance * TOP_DOWN_OFFSET
+        } else if ride_along.0 == Some(*planet) && velocity.linear != Vec3::ZERO {
+            // trail just behind and above the object, so it looks like it's plowing ahead
+            let heading = velocity.linear.normalize();
+            let behind = -glam::Vec3::new(heading.x, heading.y, heading.z) * distance;
+            behind + glam::Vec3::Y * distance * 0.3
+        } else {
+            let mut cam_pos = glam::Vec3::Z * distance;
+
+            if transform.translation.z < 0.0 {
+                cam_pos.z = -cam_pos.z;
+            }
+
+            // look down on rings at an angle, so the planet's shadow across them shows
+            if planet.has_rings(&db) {
+                cam_pos = (cam_pos + glam::Vec3::Y * distance * 0.5).normalize() * distance;
+            }
+            cam_pos
+        };
+        // the view from above can still be turned, but not tilted away from straight down
+        let pitch = if looking_down { 0.0 } else { orbit.pitch };
+        cam_pos =
+            glam::Quat::from_rotation_y(orbit.yaw) * glam::Quat::from_rotation_x(-pitch) * cam_pos;
+
+        rig.driver_mut::<Position>().position = transform.transform_2_dolly().position + cam_pos;
+    }
+}
+
+// `T` flips between the usual view of the selected object and looking straight down on it
+fn toggle_top_down(
+    kbd: Res<Input<KeyCode>>,
+    db: Res<BodyDatabase>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+    mut top_down: ResMut<TopDownView>,
+) {
+    let selected = current.get_single().ok().copied();
+    if top_down.0.is_some() && top_down.0 != selected {
+        top_down.0 = None;
+    }
+
+    if kbd.just_pressed(KeyCode::T) {
+        if let Some(obj) = selected {
+            info!("Toggled the view from above {}", obj.name(&db));
+            top_down.0 = if top_down.0.is_some() {
+                None
+            } else {
+                Some(obj)
+            };
+        }
+    }
+}
+
+pub fn track_velocity(time: Res<Time>, mut objs: Query<(&Transform, &mut Velocity)>) {
+    let delta = time.delta_seconds();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (transform, mut velocity) in objs.iter_mut() {
+        if let Some(last) = velocity.last_translation {
+            velocity.linear = (transform.translation - last) / delta;
+        }
+        velocity.last_translation = Some(transform.translation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_camera_doesnt_panic() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<CameraOrbit>()
+            .init_resource::<CameraZoom>()
+            .init_resource::<RideAlong>()
+            .init_resource::<TopDownView>()
+            .init_resource::<CameraTuning>()
+            .insert_resource(BodyDatabase::embedded())
+            .add_system(lock_to_object);
+        app.world.spawn((
+            SpaceObject::Earth,
+            Transform::default(),
+            Velocity::default(),
+            DisplayRadius(1.0),
+            VisualExtent(1.0),
+            CurrentObject,
+        ));
+
+        app.update();
+    }
+}
@@ -1,8 +1,17 @@
 #![warn(clippy::all)]
 
+use auto_spin::{AutoSpin, AutoSpinPlugin};
 use bevy::{
     core_pipeline::fxaa::{Fxaa, Sensitivity},
+    diagnostic::FrameTimeDiagnosticsPlugin,
+    ecs::{
+        schedule::ShouldRun,
+        system::{EntityCommands, SystemParam},
+    },
+    input::InputSystem,
+    pbr::NotShadowCaster,
     prelude::*,
+    transform::TransformSystem,
 };
 use bevy_dolly::{dolly::glam, prelude::*};
 use bevy_egui::{
@@ -12,17 +21,255 @@ use bevy_egui::{
 use bevy_embedded_assets::EmbeddedAssetPlugin;
 use bevy_framepace::FramepacePlugin;
 use bevy_mod_picking::{
-    InteractablePickingPlugin, PickableBundle, PickingCameraBundle, PickingEvent, PickingPlugin,
-    SelectionEvent,
+    InteractablePickingPlugin, PickableBundle, PickingCameraBundle, PickingPlugin,
 };
-use space::SpaceObject;
+use camera::CameraTuning;
+use collision::{avoid_collisions, CollisionPlugin};
+use cursor::CursorPlugin;
+use focus::{FocusPlugin, PauseWhenUnfocused};
+use gamepad::GamepadPlugin;
+use lock::{lock_to_object, LockPlugin, RideAlong, TopDownView, Velocity};
+use nearest::NearestPlugin;
+use odometer::OdometerPlugin;
+use overview::{reset_camera, OverviewPlugin, DEFAULT_CAMERA_POSITION};
+use palette::MoonPalette;
+use rings::{RingTiltDemo, RingTiltPlugin};
+use selection::{object_selected, CurrentObject, Selection, SelectionPlugin};
+use serde::{Deserialize, Serialize};
+use settings::{Persisted, PersistedAppExt};
+use simulation::{
+    apply_exaggeration, interpolate_orbits, planet_orbit, OrbitState, Paused, SimSpeed,
+    SimulationClock, SimulationPlugin,
+};
+use space::{Approach, BodyDatabase, Category, ExaggerationSettings, SpaceObject};
+use std::{collections::HashSet, marker::PhantomData};
+use sun::SunMaterial;
+use textures::{TextureFiles, TexturePack, TexturesPlugin};
+use zoom::ZoomPlugin;
 
+mod alignment;
+mod asteroids;
+mod auto_spin;
+mod axes;
+mod camera;
+#[cfg(not(target_arch = "wasm32"))]
+mod capture;
+mod clocks;
+mod collision;
+mod cursor;
+mod facts;
+mod focus;
+mod gamepad;
+mod habitable;
+#[cfg(test)]
+mod harness;
+mod illumination;
+mod labels;
+mod ladder;
+mod lighting;
+mod lock;
+mod nearest;
+mod odometer;
+mod orbits;
+mod overview;
+mod palette;
+mod presets;
+mod rings;
+mod selection;
+mod settings;
+mod share;
+mod signal;
+mod simulation;
 mod space;
+mod speeds;
+mod spotlight;
+mod starfield;
+mod stats;
+mod sun;
+mod temperature;
+mod text_mode;
+mod textures;
+mod units;
+mod zoom;
+
+/// Extra rotation applied around whatever the camera is looking at, driven by the gamepad
+/// and [`AutoSpin`].
+#[derive(Resource, Default)]
+struct CameraOrbit {
+    yaw: f32,
+    pitch: f32,
+}
+
+/// Whether the Sun casts shadows, like Saturn's shadow across its rings.
+/// Off by default on the web, where shadow maps are expensive.
+#[derive(Resource)]
+struct ShowShadows(bool);
+
+impl Default for ShowShadows {
+    fn default() -> Self {
+        Self(cfg!(not(target_arch = "wasm32")))
+    }
+}
+
+/// Whether objects are drawn with their photographic textures or as flat colors.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum RenderStyle {
+    #[default]
+    Textured,
+    /// A cleaner diagram look for presenting, colored by [`space::Category`].
+    Flat,
+}
+
+/// Both materials an object can be drawn with, swapped between by [`RenderStyle`].
+#[derive(Component)]
+struct ObjectMaterials {
+    textured: Handle<StandardMaterial>,
+    flat: Handle<StandardMaterial>,
+}
+
+/// The Sun's materials, which unlike the others isn't a [`StandardMaterial`] when textured.
+#[derive(Component)]
+struct SunMaterials {
+    textured: Handle<SunMaterial>,
+    flat: Handle<StandardMaterial>,
+}
+
+/// The smallest radius an object is drawn at, in scene units, so tiny moons like Styx stay
+/// visible and clickable. Only the drawn size changes; the info panel still shows real sizes.
+#[derive(Resource)]
+struct MinVisibleRadius(f32);
+
+impl Default for MinVisibleRadius {
+    fn default() -> Self {
+        Self(50.0)
+    }
+}
+
+/// The radius an object is actually drawn at, after [`MinVisibleRadius`].
+#[derive(Component)]
+struct DisplayRadius(f32);
+
+/// The radius an object's mesh was built with, which [`DisplayRadius`] scales from.
+#[derive(Component)]
+struct MeshRadius(f32);
+
+/// How far an object's visuals reach past its sphere, like Saturn's rings, as a multiple of
+/// [`DisplayRadius`]. Used to keep the whole thing in frame.
+#[derive(Component)]
+struct VisualExtent(f32);
+
+impl VisualExtent {
+    fn of(obj: SpaceObject, db: &BodyDatabase) -> Self {
+        Self(obj.rings(db).map_or(1.0, |(_, outer)| outer.max(1.0)))
+    }
+}
+
+/// The object to start out looking at, instead of the whole system.
+/// Set with `--start mars` natively, or `?start=mars` on the web.
+#[derive(Resource, Default)]
+struct StartObject(Option<SpaceObject>);
+
+impl StartObject {
+    fn from_args(db: &BodyDatabase) -> Self {
+        #[cfg(target_arch = "wasm32")]
+        let name = web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .and_then(|search| {
+                search
+                    .trim_start_matches('?')
+                    .split('&')
+                    .find_map(|pair| pair.strip_prefix("start=").map(str::to_string))
+            });
+        #[cfg(not(target_arch = "wasm32"))]
+        let name = std::env::args().skip_while(|arg| arg != "--start").nth(1);
+
+        Self(name.and_then(|name| SpaceObject::from_name(&name, db)))
+    }
+}
+
+/// Whether any of the windows are shown, toggled with `H` for an uncluttered view.
+#[derive(Resource, Serialize, Deserialize)]
+struct ShowUi(bool);
+
+impl Default for ShowUi {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+impl Persisted for ShowUi {
+    const KEY: &'static str = "show_ui";
+}
 
-const DEFAULT_CAMERA_POSITION: glam::Vec3 = glam::Vec3::new(0.0, 100.0, 100_000.0);
+/// Whether the objects are still being spawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum AppState {
+    Loading,
+    Running,
+}
+
+/// The objects left to spawn while [`AppState::Loading`], in reverse order.
+#[derive(Resource)]
+struct SpawnQueue {
+    pending: Vec<SpaceObject>,
+    total: usize,
+}
+
+impl Default for SpawnQueue {
+    fn default() -> Self {
+        // the Sun is spawned in `setup`, with its light
+        let pending: Vec<_> = enum_iterator::all::<SpaceObject>()
+            .filter(|obj| *obj != SpaceObject::Sun)
+            .rev()
+            .collect();
+        Self {
+            total: pending.len(),
+            pending,
+        }
+    }
+}
+
+/// The object whose button is hovered in the main window's list, if any.
+#[derive(Resource, Default)]
+struct HoveredListEntry(Option<SpaceObject>);
+
+/// Marks the object being previewed from the list, before it's clicked.
+#[derive(Component)]
+struct PreviewObject;
 
+/// The glowing shell drawn around a [`PreviewObject`].
 #[derive(Component)]
-struct CurrentObject;
+struct PreviewHalo;
+
+/// Objects the user has starred, listed at the top of the main window.
+#[derive(Resource, Default, Serialize, Deserialize)]
+struct Favorites(HashSet<SpaceObject>);
+
+impl Persisted for Favorites {
+    const KEY: &'static str = "favorites";
+}
+
+/// A star button that adds or removes `obj` from the favorites.
+fn favorite_button(ui: &mut egui::Ui, favorites: &mut ResMut<Favorites>, obj: SpaceObject) {
+    let starred = favorites.0.contains(&obj);
+
+    if ui
+        .small_button(if starred { "★" } else { "☆" })
+        .on_hover_text(if starred {
+            "Remove from favorites"
+        } else {
+            "Add to favorites"
+        })
+        .clicked()
+    {
+        // only touch the resource on click, so it isn't saved every frame
+        if starred {
+            favorites.0.remove(&obj);
+        } else {
+            favorites.0.insert(obj);
+        }
+    }
+}
 
 #[bevy_main]
 fn main() {
@@ -57,31 +304,234 @@ fn main() {
     app.add_plugin(PickingPlugin)
         .add_plugin(InteractablePickingPlugin)
         .add_plugin(FramepacePlugin)
-        .add_plugin(EguiPlugin);
+        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(EguiPlugin)
+        .add_plugin(MaterialPlugin::<SunMaterial>::default());
 
-    app.add_dolly_component(MainCamera);
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(request) = capture::CaptureRequest::from_args() {
+        app.add_plugin(capture::CapturePlugin(request));
+    }
+
+    app.add_dolly_component(MainCamera)
+        .add_plugin(SelectionPlugin)
+        .add_plugin(GamepadPlugin)
+        .add_plugin(ZoomPlugin)
+        .add_plugin(OverviewPlugin)
+        .add_plugin(LockPlugin)
+        .add_plugin(CollisionPlugin)
+        .add_plugin(OdometerPlugin)
+        .add_plugin(CursorPlugin)
+        .add_plugin(RingTiltPlugin)
+        .add_plugin(AutoSpinPlugin)
+        .add_plugin(NearestPlugin)
+        .add_plugin(SimulationPlugin)
+        .add_plugin(FocusPlugin)
+        .add_plugin(TexturesPlugin);
+
+    // `--start` is looked up by name, so it needs the data before the app takes it
+    let db = BodyDatabase::embedded();
+    let start = StartObject::from_args(&db);
+
+    app.init_resource::<CameraOrbit>()
+        .init_resource::<CameraTuning>()
+        .init_resource::<ShowShadows>()
+        .init_resource::<RenderStyle>()
+        .init_resource::<MoonPalette>()
+        .init_resource::<MinVisibleRadius>()
+        .insert_resource(db)
+        .init_resource::<ExaggerationSettings>()
+        .insert_resource(share::PendingSceneState::from_url())
+        .insert_resource(start)
+        .init_resource::<HoveredListEntry>()
+        .init_resource::<temperature::TemperatureTint>()
+        .init_resource::<clocks::ShowOrbitClocks>()
+        .init_resource::<axes::ShowAxes>()
+        .init_resource::<habitable::ShowHabitableZone>()
+        .init_resource::<asteroids::ShowAsteroidBelt>()
+        .init_resource::<spotlight::Spotlight>()
+        .init_resource::<sun::DimSunNearby>()
+        .init_resource::<illumination::Illumination>()
+        .init_resource::<lighting::FillLight>()
+        .init_resource::<lighting::ToneMapping>()
+        .init_resource::<orbits::OrbitDisplay>()
+        .init_resource::<alignment::Alignment>()
+        .init_resource::<ladder::DistanceLadder>()
+        .init_persisted_resource::<Favorites>()
+        .init_persisted_resource::<units::UnitSystem>()
+        .init_persisted_resource::<text_mode::TextOnlyMode>()
+        .init_persisted_resource::<ShowUi>()
+        .init_persisted_resource::<stats::ShowStatsStrip>()
+        .init_persisted_resource::<labels::ShowLabels>();
+
+    app.add_state(AppState::Loading)
+        .init_resource::<SpawnQueue>()
+        .add_system_set(
+            SystemSet::on_update(AppState::Loading)
+                .with_system(spawn_objects)
+                .with_system(loading_ui),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::Running)
+                .with_system(share::apply_scene_state.after(object_selected)),
+        );
+
+    app.add_startup_system(setup)
+        .add_startup_system(lighting::spawn_fill_light);
+
+    app.add_system_to_stage(
+        CoreStage::PostUpdate,
+        starfield::follow_camera
+            .after(avoid_collisions)
+            .before(TransformSystem::TransformPropagate),
+    );
+    // before anything reads the keyboard this frame
+    app.add_system_to_stage(
+        CoreStage::PreUpdate,
+        release_keys_for_egui.after(InputSystem),
+    );
+    app.add_system(
+        alignment::detect_alignment
+            .after(planet_orbit)
+            .before(interpolate_orbits),
+    )
+    .add_system(camera::apply_camera_tuning)
+    .add_system(camera::fit_far_plane.after(interpolate_orbits))
+    .add_system(
+        camera::damp_camera_prediction
+            .after(camera::apply_camera_tuning)
+            .after(lock_to_object),
+    )
+    .add_system(apply_shadows)
+    .add_system(lighting::apply_fill_light)
+    .add_system(lighting::apply_tone_mapping)
+    .add_system(orbits::draw_orbits.after(apply_exaggeration))
+    .add_system(
+        orbits::follow_orbited_objects
+            .after(orbits::draw_orbits)
+            .after(interpolate_orbits),
+    )
+    .add_system(habitable::draw_habitable_zone.after(apply_exaggeration))
+    .add_system(asteroids::spawn_asteroid_belt)
+    .add_system(
+        asteroids::move_asteroids
+            .after(planet_orbit)
+            .after(apply_exaggeration),
+    )
+    .add_system(
+        spotlight::apply_spotlight
+            .after(object_selected)
+            .after(orbits::draw_orbits)
+            .after(habitable::draw_habitable_zone)
+            .after(asteroids::spawn_asteroid_belt),
+    )
+    .add_system(apply_render_style)
+    .add_system(starfield::apply_render_style)
+    .add_system(sun::dim_sun.after(apply_render_style))
+    .add_system(palette::apply_moon_palette)
+    .add_system(illumination::apply_illumination.after(palette::apply_moon_palette))
+    .add_system(preview_hovered.after(main_ui))
+    .add_system(temperature::apply_temperature_tint)
+    .add_system(apply_display_size.after(apply_exaggeration))
+    .add_system(ladder::distance_ladder.after(reset_camera));
+
+    app.add_system(signal::light_signal);
 
-    app.add_startup_system(setup);
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    app.add_system(space::reload_body_data);
 
-    app.add_system(object_selected)
-        .add_system(planet_orbit)
-        .add_system(lock_to_object.after(object_selected).after(planet_orbit))
-        .add_system(escape.after(object_selected))
-        .add_system(reset_camera.after(escape).after(lock_to_object));
+    app.add_system(toggle_ui);
 
-    app.add_system(main_ui).add_system(obj_info_ui);
+    app.add_system_set(
+        SystemSet::new()
+            .with_run_criteria(ui_visible)
+            .with_system(main_ui)
+            .with_system(obj_info_ui)
+            .with_system(signal::signal_ui)
+            .with_system(facts::facts_ui)
+            .with_system(speeds::speeds_ui)
+            .with_system(ladder::ladder_caption)
+            .with_system(alignment::alignment_ui)
+            .with_system(lighting::effects_ui)
+            .with_system(text_mode::text_only_ui)
+            .with_system(stats::stats_strip)
+            .with_system(clocks::orbit_clocks.after(interpolate_orbits))
+            .with_system(clocks::date_hud)
+            .with_system(labels::object_labels.after(interpolate_orbits))
+            .with_system(axes::rotation_axis.after(interpolate_orbits))
+            .with_system(share::share_ui)
+            .with_system(temperature::temperature_legend),
+    );
 
     app.run()
 }
 
+/// Tags both halves of the camera: the entity with the dolly [`Rig`], and the one with the
+/// [`Camera3dBundle`] that actually renders. bevy_dolly copies the rig's output onto the
+/// rendering one's [`Transform`] whenever the rig changes, so anything moving the camera for more
+/// than a frame should steer the rig's drivers, like `lock_to_object` and `reset_camera` do.
+/// Writing the [`Transform`] directly only sticks while nothing touches the rig.
 #[derive(Component)]
 struct MainCamera;
 
+/// The extra things `main_ui` can turn on and draw over the scene.
+#[derive(SystemParam)]
+struct Overlays<'w, 's> {
+    tint: ResMut<'w, temperature::TemperatureTint>,
+    clocks: ResMut<'w, clocks::ShowOrbitClocks>,
+    labels: ResMut<'w, labels::ShowLabels>,
+    text_only: ResMut<'w, text_mode::TextOnlyMode>,
+    stats_strip: ResMut<'w, stats::ShowStatsStrip>,
+    #[system_param(ignore)]
+    _marker: PhantomData<&'s ()>,
+}
+
+/// How fast `main_ui` runs the simulation.
+#[derive(SystemParam)]
+struct Playback<'w, 's> {
+    paused: ResMut<'w, Paused>,
+    speed: ResMut<'w, SimSpeed>,
+    pause_when_unfocused: ResMut<'w, PauseWhenUnfocused>,
+    #[system_param(ignore)]
+    _marker: PhantomData<&'s ()>,
+}
+
+/// Which textures `main_ui` draws the objects with.
+#[derive(SystemParam)]
+struct TextureSettings<'w, 's> {
+    pack: ResMut<'w, TexturePack>,
+    detail: ResMut<'w, textures::TextureDetail>,
+    #[system_param(ignore)]
+    _marker: PhantomData<&'s ()>,
+}
+
+/// How the list of objects in `main_ui` is shown.
+#[derive(Default)]
+struct ObjectList {
+    sort_by_distance: bool,
+    /// Only objects with this in their name are listed, ignoring case.
+    filter: String,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn main_ui(
-    mut commands: Commands,
+    mut selection: Selection,
     mut egui_ctx: ResMut<EguiContext>,
-    objs: Query<(Entity, &SpaceObject)>,
+    mut favorites: ResMut<Favorites>,
+    mut tuning: ResMut<CameraTuning>,
+    mut shadows: ResMut<ShowShadows>,
+    mut style: ResMut<RenderStyle>,
+    mut texture_settings: TextureSettings,
+    mut realism: presets::RealismSettings,
+    mut playback: Playback,
+    mut spin: ResMut<AutoSpin>,
+    mut overlays: Overlays,
+    mut hovered_entry: ResMut<HoveredListEntry>,
+    db: Res<BodyDatabase>,
+    mut list: Local<ObjectList>,
 ) {
+    let mut hovered = None;
+
     egui::Window::new("Solar System")
         .default_width(300.0)
         .show(egui_ctx.ctx_mut(), |ui| {
@@ -93,30 +543,303 @@ fn main_ui(
             ui.label("You can click both the planet in the simulation and the planet in the list to zoom in.");
             ui.separator();
             ui.label("Press 'Esc' to reset the camera.");
+            ui.label("Drag with the right mouse button to look around the overview.");
+            ui.label("Press 'H' to hide these windows, and again to bring them back.");
+            ui.label("Press '.' to move the selected object on by a quarter of its orbit.");
+            ui.label("Press ',' to move the simulation on by one day.");
+            ui.label("Press 'T' to look straight down on the selected object.");
+            ui.label("Press 'Space' to pause or resume the simulation.");
+            ui.label("Press the left and right arrow keys to step through every object.");
+            ui.label("Scroll to zoom in and out on the selected object.");
             ui.separator();
+            if !favorites.0.is_empty() {
+                ui.label("Favorites");
+                ui.horizontal_wrapped(|ui| {
+                    for obj in enum_iterator::all::<SpaceObject>().filter(|o| favorites.0.contains(o)) {
+                        if ui.small_button(obj.name(&db)).clicked() {
+                            selection.select(obj);
+                        }
+                    }
+                });
+                ui.separator();
+            }
+            let current = realism.preset();
+            egui::ComboBox::from_label("Preset")
+                .selected_text(current.map_or("Custom", presets::RealismPreset::name))
+                .show_ui(ui, |ui| {
+                    for preset in enum_iterator::all::<presets::RealismPreset>() {
+                        if ui
+                            .selectable_label(current == Some(preset), preset.name())
+                            .clicked()
+                        {
+                            realism.apply(preset);
+                        }
+                    }
+                    // only a label, since it's whatever the settings below have been changed to
+                    ui.add_enabled(false, egui::SelectableLabel::new(current.is_none(), "Custom"));
+                });
+            let mut show_shadows = shadows.0;
+            if ui.checkbox(&mut show_shadows, "Show shadows").changed() {
+                shadows.0 = show_shadows;
+            }
+            ui.horizontal(|ui| {
+                let mut edited = *style;
+                ui.radio_value(&mut edited, RenderStyle::Textured, "Textured");
+                ui.radio_value(&mut edited, RenderStyle::Flat, "Flat colors");
+                if edited != *style {
+                    *style = edited;
+                }
+            });
+            // there's nothing to pick between until a second pack has its images
+            if enum_iterator::cardinality::<TexturePack>() > 1 {
+                let mut edited = *texture_settings.pack;
+                egui::ComboBox::from_label("Textures")
+                    .selected_text(edited.name())
+                    .show_ui(ui, |ui| {
+                        for pack in enum_iterator::all::<TexturePack>() {
+                            ui.selectable_value(&mut edited, pack, pack.name());
+                        }
+                    });
+                if edited != *texture_settings.pack {
+                    *texture_settings.pack = edited;
+                }
+            }
+            let mut edited = *texture_settings.detail;
+            egui::ComboBox::from_label("Texture detail")
+                .selected_text(edited.name())
+                .show_ui(ui, |ui| {
+                    for detail in enum_iterator::all::<textures::TextureDetail>() {
+                        ui.selectable_value(&mut edited, detail, detail.name());
+                    }
+                })
+                .response
+                .on_hover_text("Sharper close-ups of the selected object, at the cost of video memory");
+            if edited != *texture_settings.detail {
+                *texture_settings.detail = edited;
+            }
+            ui.horizontal(|ui| {
+                ui.label("Moon colors:");
+                let mut edited = *realism.palette;
+                ui.radio_value(&mut edited, MoonPalette::Artistic, "Artistic");
+                ui.radio_value(&mut edited, MoonPalette::Realistic, "Realistic");
+                if edited != *realism.palette {
+                    *realism.palette = edited;
+                }
+            });
+            let mut edited = realism.min_radius.0;
+            ui.add(egui::Slider::new(&mut edited, 0.0..=500.0).text("Minimum size"))
+                .on_hover_text("Draws tiny moons at least this big so they can be seen and clicked");
+            if edited != realism.min_radius.0 {
+                realism.min_radius.0 = edited;
+            }
+            let mut tinted = overlays.tint.0;
+            if ui.checkbox(&mut tinted, "Tint by temperature").changed() {
+                overlays.tint.0 = tinted;
+            }
+            let mut show_labels = overlays.labels.0;
+            if ui.checkbox(&mut show_labels, "Show labels").changed() {
+                overlays.labels.0 = show_labels;
+            }
+            let mut show_clocks = overlays.clocks.0;
+            if ui
+                .checkbox(&mut show_clocks, "Orbit clocks")
+                .on_hover_text("Shows how far through its year each planet is")
+                .changed()
+            {
+                overlays.clocks.0 = show_clocks;
+            }
+            let mut listed = overlays.text_only.0;
+            if ui
+                .checkbox(&mut listed, "Text-only list")
+                .on_hover_text("Lists everything about every object as text, for screen readers")
+                .changed()
+            {
+                overlays.text_only.0 = listed;
+            }
+            let mut show_orbits = *realism.orbits != orbits::OrbitDisplay::Hidden;
+            if ui
+                .checkbox(&mut show_orbits, "Show orbit paths")
+                .on_hover_text("Pick lines or tubes in the Effects window")
+                .changed()
+            {
+                *realism.orbits = if show_orbits {
+                    orbits::OrbitDisplay::Lines
+                } else {
+                    orbits::OrbitDisplay::Hidden
+                };
+            }
+            let mut strip = overlays.stats_strip.0;
+            if ui
+                .checkbox(&mut strip, "Stats strip")
+                .on_hover_text("Shows what's selected, what's nearby and how smoothly it's running along the bottom")
+                .changed()
+            {
+                overlays.stats_strip.0 = strip;
+            }
+            ui.horizontal(|ui| {
+                let paused = playback.paused.0;
+                ui.label(if paused { "Paused" } else { "Running" });
+                let button = ui.button(if paused { "Resume" } else { "Pause" });
+                if button.clicked() {
+                    playback.paused.0 = !paused;
+                    // a focused button is pressed by `Space` too, which would undo the toggle
+                    button.surrender_focus();
+                }
+            });
+            ui.horizontal(|ui| {
+                let mut edited = playback.speed.0;
+                ui.add(
+                    egui::Slider::new(&mut edited, 0.0..=SimSpeed::MAX)
+                        .logarithmic(true)
+                        .suffix("x")
+                        .text("Speed"),
+                )
+                .on_hover_text("Speed up to watch the outer planets go round, or drag to 0 to stop everything");
+                if ui.small_button("1x").clicked() {
+                    edited = 1.0;
+                }
+                if edited != playback.speed.0 {
+                    playback.speed.0 = edited;
+                }
+            });
+            let mut pause = playback.pause_when_unfocused.0;
+            if ui
+                .checkbox(&mut pause, "Pause in the background")
+                .on_hover_text("Stops the simulation while the window isn't focused, to save battery")
+                .changed()
+            {
+                playback.pause_when_unfocused.0 = pause;
+            }
+            let mut spinning = spin.0;
+            if ui
+                .checkbox(&mut spinning, "Spin around when idle")
+                .on_hover_text("Slowly circles the selected object after a few seconds without input")
+                .changed()
+            {
+                spin.0 = spinning;
+            }
+            ui.collapsing("Camera", |ui| {
+                // edit a copy so the rig is only rebuilt when something actually changes
+                let mut edited = *tuning;
+                edited.ui(ui);
+                if edited != *tuning {
+                    *tuning = edited.clamped();
+                }
+            });
+            ui.separator();
+            ui.checkbox(&mut list.sort_by_distance, "Sort by distance from the Sun");
+            ui.horizontal(|ui| {
+                ui.label("Find:");
+                ui.text_edit_singleline(&mut list.filter);
+                if !list.filter.is_empty() && ui.small_button("Clear").clicked() {
+                    list.filter.clear();
+                }
+            });
+            let filter = list.filter.trim().to_lowercase();
+            let listed: Vec<SpaceObject> = if list.sort_by_distance {
+                SpaceObject::all_in_orbit_order(&db)
+            } else {
+                enum_iterator::all::<SpaceObject>().collect()
+            }
+            .into_iter()
+            .filter(|obj| obj.name(&db).to_lowercase().contains(&filter))
+            .collect();
+            if listed.is_empty() {
+                ui.label("Nothing matches that name.");
+            }
+            // each planet starts a new row with its moons after it, even if it's filtered out
+            let mut row = None;
             egui::Grid::new("planets").show(ui, |ui| {
-            for obj in enum_iterator::all::<SpaceObject>() {
-                if obj.orbits() == SpaceObject::Sun {
+            for obj in listed {
+                let planet = if obj.orbits(&db) == SpaceObject::Sun { obj } else { obj.orbits(&db) };
+                if row != Some(planet) {
                     ui.end_row();
+                    row = Some(planet);
                 }
 
-                if ui.small_button(obj.name()).clicked() {
-                    for (entity, &other_obj) in objs.iter() {
-                        let mut entity = commands.entity(entity);
-
-                        if other_obj == obj {
-                           entity.insert(CurrentObject);
-                        } else {
-                            entity.remove::<CurrentObject>();
-                        }
+                ui.horizontal(|ui| {
+                    let button = ui.small_button(obj.name(&db));
+                    if button.hovered() {
+                        hovered = Some(obj);
                     }
-                }
+                    if button.clicked() {
+                        selection.select(obj);
+                    }
+                    favorite_button(ui, &mut favorites, obj);
+                });
             }});
         });
+
+    if hovered_entry.0 != hovered {
+        hovered_entry.0 = hovered;
+    }
 }
 
+// highlight whatever is hovered in the list, so it's easy to find in the scene
+fn preview_hovered(
+    mut commands: Commands,
+    hovered: Res<HoveredListEntry>,
+    objs: Query<(Entity, &SpaceObject, &MeshRadius)>,
+    previews: Query<Entity, With<PreviewObject>>,
+    halos: Query<Entity, With<PreviewHalo>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !hovered.is_changed() {
+        return;
+    }
+
+    for entity in previews.iter() {
+        commands.entity(entity).remove::<PreviewObject>();
+    }
+    for halo in halos.iter() {
+        commands.entity(halo).despawn_recursive();
+    }
+
+    let Some((entity, _, mesh_radius)) = hovered
+        .0
+        .and_then(|hovered| objs.iter().find(|(_, obj, _)| **obj == hovered))
+    else {
+        return;
+    };
+
+    commands
+        .entity(entity)
+        .insert(PreviewObject)
+        .with_children(|children| {
+            children.spawn((
+                PbrBundle {
+                    // a child, so it's scaled up along with tiny moons
+                    mesh: meshes.add(Mesh::from(shape::UVSphere {
+                        radius: mesh_radius.0 * 1.15,
+                        sectors: 32,
+                        stacks: 32,
+                    })),
+                    material: materials.add(StandardMaterial {
+                        base_color: Color::rgba(1.0, 1.0, 1.0, 0.25),
+                        alpha_mode: AlphaMode::Blend,
+                        unlit: true,
+                        ..default()
+                    }),
+                    ..default()
+                },
+                NotShadowCaster,
+                PreviewHalo,
+            ));
+        });
+}
+
+/// Formats a number like "5.972x10^24". Zero, infinities and NaN are written out as they are,
+/// since they have no exponent to pull out.
 fn scientific_notation(num: f32) -> String {
-    let mut num = num;
+    if num == 0.0 {
+        return "0".to_string();
+    }
+    if !num.is_finite() {
+        return num.to_string();
+    }
+    let sign = if num < 0.0 { "-" } else { "" };
+    let mut num = num.abs();
     let mut exp = 0;
 
     while num >= 10.0 {
@@ -129,57 +852,385 @@ fn scientific_notation(num: f32) -> String {
         exp -= 1;
     }
 
-    format!("{num}x10^{exp}")
+    format!("{sign}{num}x10^{exp}")
 }
 
-fn obj_info_ui(mut egui_ctx: ResMut<EguiContext>, obj: Query<&SpaceObject, With<CurrentObject>>) {
-    if let Ok(obj) = obj.get_single() {
-        egui::Window::new(obj.name())
-            .default_width(300.0)
-            .show(egui_ctx.ctx_mut(), |ui| {
-                ui.label(obj.name());
-                ui.separator();
-                ui.label(format!("Mass: {} kg", scientific_notation(obj.mass())));
-                ui.label(format!("Diameter: {} km", obj.radius() * 2.0));
-                ui.label(format!(
-                    "Distance from what it orbits: {} AU",
-                    obj.distance()
-                ));
-                ui.label(format!("Number of moons: {}", obj.num_moons()));
-                ui.label(format!("Average temperature: {}°C", obj.temperature()));
-                ui.label(format!(
-                    "Period of revolution: {} days",
-                    obj.period_of_revolution()
-                ));
-                ui.label(format!(
-                    "Period of rotation: {} days",
-                    obj.period_of_rotation()
-                ));
-                ui.label(format!("Orbits: {}", obj.orbits().name()));
-                ui.separator();
-                ui.label(format!("Fun fact: {}", obj.fun_fact()));
+/// Formats a number with commas between each group of three digits, like "12,742", leaving any
+/// decimals as they are. Huge numbers like masses read better with [`scientific_notation`].
+fn with_separators(num: f32) -> String {
+    let formatted = num.to_string();
+    let (sign, unsigned) = match formatted.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", formatted.as_str()),
+    };
+    let (whole, decimals) = match unsigned.split_once('.') {
+        Some((whole, decimals)) => (whole, format!(".{decimals}")),
+        None => (unsigned, String::new()),
+    };
+
+    let mut grouped = String::with_capacity(whole.len() + whole.len() / 3);
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    format!("{sign}{grouped}{decimals}")
+}
+
+/// The angle of an object around what it orbits, matching the direction `planet_orbit` moves it.
+fn orbital_angle(translation: Vec3) -> f32 {
+    (-translation.z).atan2(translation.x)
+}
+
+// the numbers shown for an object, both in its info window and in the text-only list
+fn object_stats(ui: &mut egui::Ui, obj: SpaceObject, db: &BodyDatabase, units: units::UnitSystem) {
+    ui.label(format!("Mass: {}", units.mass(obj.mass(db))));
+    ui.label(format!("Diameter: {}", units.length(obj.radius(db) * 2.0)));
+    ui.label(format!(
+        "Surface gravity: {} ({:.2} g)",
+        units.acceleration(obj.surface_gravity(db)),
+        obj.surface_gravity(db) / EARTH_GRAVITY
+    ));
+    ui.label(format!(
+        "Escape velocity: {}",
+        units.speed(obj.escape_velocity(db) / 1_000.0)
+    ));
+    let density = obj.density(db);
+    ui.label(if density < WATER_DENSITY {
+        format!("Density: {}, less than water", units.density(density))
+    } else {
+        format!("Density: {}", units.density(density))
+    });
+    if obj.orbits(db) != SpaceObject::Sun {
+        ui.label(format!(
+            "Distance from {}: {} AU",
+            obj.orbits(db).name(db),
+            with_separators(obj.distance_from_parent(db))
+        ));
+    }
+    ui.label(format!(
+        "Distance from the Sun: {} AU",
+        with_separators(obj.distance_from_sun(db))
+    ));
+    let (modeled, known) = (obj.num_moons(db), obj.number_of_known_moons(db));
+    ui.label(if modeled == known {
+        format!("Number of moons: {known}")
+    } else {
+        format!("Number of moons: {modeled} shown here, {known} known")
+    });
+    ui.label(format!(
+        "Average temperature: {}",
+        units.temperature(obj.temperature(db))
+    ));
+    ui.label(format!(
+        "Period of revolution: {} days",
+        with_separators(obj.period_of_revolution(db))
+    ));
+    ui.label(format!(
+        "Period of rotation: {} days",
+        with_separators(obj.period_of_rotation(db))
+    ));
+    ui.label(format!("Axial tilt: {}°", obj.axial_tilt(db)));
+    ui.label(format!("Orbits: {}", obj.orbits(db).name(db)));
+    if obj != SpaceObject::Sun {
+        ui.label(if obj.orbits_retrograde(db) {
+            "Orbit direction: retrograde (backwards)"
+        } else {
+            "Orbit direction: prograde"
+        });
+    }
+    for resonance in obj.resonances(db) {
+        let (p, q) = resonance.ratio;
+        ui.label(format!(
+            "In {p}:{q} orbital resonance with {}",
+            resonance.with.name(db)
+        ));
+    }
+}
+
+fn advanced_stats(ui: &mut egui::Ui, obj: SpaceObject, db: &BodyDatabase) {
+    ui.label(match obj.magnetic_field_strength(db) {
+        Some(strength) if strength > 0.0 => {
+            format!("Magnetic field: {strength}x Earth's")
+        }
+        Some(_) => "Magnetic field: none".to_string(),
+        None => "Magnetic field: unknown".to_string(),
+    });
+    ui.label(format!("Number of rings: {}", obj.number_of_rings(db)));
+}
+
+/// The density of water in kilograms per cubic meter. Anything less dense would float in it.
+const WATER_DENSITY: f32 = 1_000.0;
+
+/// Earth's surface gravity in meters per second squared.
+const EARTH_GRAVITY: f32 = 9.81;
+
+/// How fast a person leaves the ground when they jump, in meters per second (about half a meter
+/// high on Earth).
+const JUMP_SPEED: f32 = 3.0;
+
+/// The temperatures someone could survive in for a while without a spacesuit, in °C.
+const SURVIVABLE_TEMPERATURES: std::ops::RangeInclusive<f32> = -40.0..=50.0;
+
+// what it'd be like to visit, worked out from the real numbers
+fn survival_facts(
+    ui: &mut egui::Ui,
+    obj: SpaceObject,
+    db: &BodyDatabase,
+    units: units::UnitSystem,
+) {
+    let gravity = obj.surface_gravity(db);
+    let solid = matches!(
+        obj.category(),
+        Category::Terrestrial | Category::DwarfPlanet | Category::Moon
+    );
+
+    if !solid {
+        ui.label("There's no solid ground to stand on, so you'd sink right in.");
+    }
+    ui.label(format!(
+        "You'd weigh {:.2} times what you do on Earth.",
+        gravity / EARTH_GRAVITY
+    ));
+    if obj.escape_velocity(db) < JUMP_SPEED {
+        ui.label("You could jump hard enough to leave it behind and float off into space!");
+    } else if solid {
+        let escape = obj.escape_velocity(db);
+        let escape = if escape < 1_000.0 {
+            format!("{escape:.0} m/s")
+        } else {
+            format!("{:.1} km/s", escape / 1_000.0)
+        };
+        ui.label(format!(
+            "A jump would take you {} m high, but you'd need to go {escape} to escape.",
+            with_separators((JUMP_SPEED * JUMP_SPEED / (2.0 * gravity) * 10.0).round() / 10.0)
+        ));
+    }
+
+    let temperature = obj.temperature(db);
+    let shown = units.temperature(temperature);
+    ui.label(if temperature < *SURVIVABLE_TEMPERATURES.start() {
+        format!("At {shown}, you'd freeze solid.")
+    } else if temperature > *SURVIVABLE_TEMPERATURES.end() {
+        format!("At {shown}, you'd be cooked.")
+    } else {
+        format!("At {shown}, the temperature wouldn't be the problem.")
+    });
+
+    let day = obj.period_of_rotation(db);
+    ui.label(if day < 1.0 {
+        format!("A day lasts {:.1} hours.", day * 24.0)
+    } else {
+        format!("A day lasts {} Earth days.", with_separators(day))
+    });
+}
+
+/// The closest approach last worked out in the info window, so it's only redone when the pair
+/// changes or the approach has passed.
+struct CachedApproach {
+    pair: (SpaceObject, SpaceObject),
+    /// The simulated day it was worked out on.
+    from_day: f64,
+    approach: Option<Approach>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn obj_info_ui(
+    mut selection: Selection,
+    mut egui_ctx: ResMut<EguiContext>,
+    mut favorites: ResMut<Favorites>,
+    mut ride_along: ResMut<RideAlong>,
+    mut top_down: ResMut<TopDownView>,
+    mut ring_demo: ResMut<RingTiltDemo>,
+    mut units: ResMut<units::UnitSystem>,
+    db: Res<BodyDatabase>,
+    clock: Res<SimulationClock>,
+    obj: Query<(&SpaceObject, &Transform, &OrbitState), With<CurrentObject>>,
+    objs: Query<(&SpaceObject, &OrbitState)>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut compare_to: Local<Option<SpaceObject>>,
+    mut cached_approach: Local<Option<CachedApproach>>,
+) {
+    if let Ok((obj, transform, state)) = obj.get_single() {
+        let window =
+            egui::Window::new(obj.name(&db))
+                .default_width(300.0)
+                .show(egui_ctx.ctx_mut(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(obj.name(&db));
+                        favorite_button(ui, &mut favorites, *obj);
+                    });
+                    ui.separator();
+                    object_stats(ui, *obj, &db, *units);
+                    ui.horizontal(|ui| {
+                        ui.label("Units:");
+                        let mut edited = *units;
+                        for system in enum_iterator::all::<units::UnitSystem>() {
+                            ui.radio_value(&mut edited, system, system.name());
+                        }
+                        if edited != *units {
+                            *units = edited;
+                        }
+                    });
+                    ui.collapsing("Advanced", |ui| advanced_stats(ui, *obj, &db));
+                    ui.collapsing("Could you live here?", |ui| {
+                        survival_facts(ui, *obj, &db, *units)
+                    });
+                    if obj.moons(&db).next().is_some() {
+                        ui.collapsing("What orbits here", |ui| {
+                            for moon in obj.moons(&db) {
+                                ui.horizontal(|ui| {
+                                    if ui.button(moon.name(&db)).clicked() {
+                                        selection.select(moon);
+                                    }
+                                    ui.label(format!(
+                                        "{} away",
+                                        units.length(moon.distance_from_parent_km(&db).round())
+                                    ));
+                                });
+                            }
+                        });
+                    }
+                    ui.separator();
+                    ui.label(format!("Fun fact: {}", obj.fun_fact(&db)));
+
+                    if *obj != SpaceObject::Sun {
+                        ui.separator();
+                        let mut riding = ride_along.0 == Some(*obj);
+                        if ui.checkbox(&mut riding, "Ride along").changed() {
+                            ride_along.0 = riding.then_some(*obj);
+                        }
+                    }
+                    let mut from_above = top_down.0 == Some(*obj);
+                    if ui
+                        .checkbox(&mut from_above, "View from above")
+                        .on_hover_text(
+                            "Looks straight down on it, to see its pole and anything orbiting it",
+                        )
+                        .changed()
+                    {
+                        top_down.0 = from_above.then_some(*obj);
+                    }
+
+                    if obj.has_rings(&db) {
+                        ui.separator();
+                        if ring_demo.0.is_some() {
+                            ui.label(
+                            "As it orbits the Sun, the rings tilt towards us and then away again. \
+                            They're so thin that they all but vanish when seen edge-on, \
+                            which happens about every 15 years.",
+                        );
+                        } else if ui.button("Demonstrate ring tilt").clicked() {
+                            ring_demo.0 = Some(0.0);
+                        }
+                    }
+
+                    if obj.orbits(&db) == SpaceObject::Sun && *obj != SpaceObject::Sun {
+                        ui.separator();
+                        ui.collapsing("Closest approach", |ui| {
+                            egui::ComboBox::from_label("to")
+                                .selected_text(
+                                    compare_to.map_or("Choose a planet", |o| o.name(&db)),
+                                )
+                                .show_ui(ui, |ui| {
+                                    for other in enum_iterator::all::<SpaceObject>().filter(|o| {
+                                        o.orbits(&db) == SpaceObject::Sun
+                                            && *o != SpaceObject::Sun
+                                            && o != obj
+                                    }) {
+                                        ui.selectable_value(
+                                            &mut *compare_to,
+                                            Some(other),
+                                            other.name(&db),
+                                        );
+                                    }
+                                });
+
+                            let Some(other) = *compare_to else {
+                                return;
+                            };
+                            let today = clock.shown_elapsed()
+                                / SpaceObject::simulated_seconds_per_day(&db) as f64;
+                            let stale = db.is_changed()
+                                || cached_approach.as_ref().map_or(true, |cached| {
+                                    cached.pair != (*obj, other)
+                                        || cached.approach.map_or(false, |approach| {
+                                            cached.from_day + (approach.days as f64) < today
+                                        })
+                                });
+                            if stale {
+                                let other_state = objs.iter().find(|(o, _)| **o == other);
+                                *cached_approach =
+                                    other_state.map(|(_, other_state)| CachedApproach {
+                                        pair: (*obj, other),
+                                        from_day: today,
+                                        approach: obj.closest_approach(
+                                            state.angle,
+                                            other,
+                                            other_state.angle,
+                                            &db,
+                                        ),
+                                    });
+                            }
+
+                            if let Some(CachedApproach {
+                                from_day,
+                                approach: Some(approach),
+                                ..
+                            }) = &*cached_approach
+                            {
+                                ui.label(format!(
+                                    "Closest to {} in {:.0} days, at {:.3} AU",
+                                    other.name(&db),
+                                    approach.days as f64 - (today - from_day),
+                                    approach.distance
+                                ));
+                            }
+                        });
+                    }
+                });
+
+        // a faint line from the window to the object, for when they're far apart on screen
+        let screen = cameras
+            .get_single()
+            .ok()
+            .and_then(|(camera, camera_transform)| {
+                camera::world_to_screen(camera, camera_transform, transform.translation)
             });
+        if let (Some(window), Some(screen)) = (window, screen) {
+            let rect = window.response.rect;
+            if !rect.contains(screen) {
+                egui_ctx
+                    .ctx_mut()
+                    .layer_painter(egui::LayerId::background())
+                    .line_segment(
+                        [rect.clamp(screen), screen],
+                        egui::Stroke::new(1.0, egui::Color32::from_white_alpha(60)),
+                    );
+            }
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut sun_materials: ResMut<Assets<SunMaterial>>,
+    tuning: Res<CameraTuning>,
+    shadows: Res<ShowShadows>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    textures: Res<TextureFiles>,
+    start: Res<StartObject>,
 ) {
     commands.spawn((
         MainCamera,
-        Rig::builder()
-            .with(Position::new(DEFAULT_CAMERA_POSITION))
-            .with(Smooth::new_position(1.0).predictive(true))
-            .with(Smooth::new_position(2.5))
-            .with(
-                LookAt::new(glam::Vec3::ZERO)
-                    .tracking_smoothness(1.25)
-                    .tracking_predictive(true),
-            )
-            .build(),
+        tuning.build_rig(DEFAULT_CAMERA_POSITION, glam::Vec3::ZERO),
     ));
 
     commands.spawn((
@@ -191,225 +1242,354 @@ fn setup(
             ..default()
         },
     ));
+    starfield::spawn_starfield(&mut commands, &asset_server, &mut meshes, &mut materials);
 
-    // planets
-    macro_rules! object {
-        ($name:ident) => {
-            object!($name, StandardMaterial::default(), t, true)
-        };
-
-        ($name:ident, $color:expr) => {
-            object!(
-                $name,
-                StandardMaterial {
-                    base_color: $color,
-                    ..default()
-                },
-                t,
-                false
-            )
-        };
-
-        ($name:ident, $material:expr, $texture:ident, $has_texture:literal) => {{
-            let obj = SpaceObject::$name;
-            let mesh = Mesh::from(shape::UVSphere {
-                radius: obj.scaled_radius(),
+    let sun_material = sun_materials.add(SunMaterial {
+        texture: asset_server.load(
+            textures
+                .texture(TexturePack::Photographic, SpaceObject::Sun, false)
+                .expect("the Sun always has a texture"),
+        ),
+        brightness: Vec4::ONE,
+    });
+    let mut sun = commands.spawn((
+        MaterialMeshBundle {
+            mesh: meshes.add(Mesh::from(shape::UVSphere {
+                radius: SpaceObject::Sun.scaled_radius(&db, &exaggeration),
                 sectors: 64,
                 stacks: 64,
-            });
-
-            let $texture = if $has_texture {
-                Some(asset_server.load(format!("{}.jpg", stringify!($name).to_lowercase())))
-            } else {
-                None
-            };
-
-            let mut obj_id = commands.spawn((
-                PbrBundle {
-                    mesh: meshes.add(mesh),
-                    material: materials.add(StandardMaterial {
-                        base_color_texture: $texture.clone(),
-                        // not reflective
-                        reflectance: 0.0,
-                        metallic: 0.0,
-                        ..$material
-                    }),
-                    transform: {
-                        let mut t = Transform::from_xyz(obj.scaled_distance(), 0.0, 0.0);
-                        // flip the planet so it's not sideways
-                        t.rotate_x(90.0_f32.to_radians());
-                        t
-                    },
-                    ..default()
-                },
-                PickableBundle::default(), // <- Makes the mesh pickable.
-            ));
-            obj_id.insert(obj);
-            obj_id
-        }};
-    }
-
-    object!(
-        Sun,
-        StandardMaterial {
-            emissive: Color::rgb_linear(255.0, 255.0, 255.0),
-            emissive_texture: texture,
+            })),
+            material: sun_material.clone(),
+            transform: Transform::from_rotation(SpaceObject::Sun.pole_orientation(&db)),
             ..default()
         },
-        texture,
-        true
-    )
-    .with_children(|children| {
+        PickableBundle::default(),
+        SpaceObject::Sun,
+        Velocity::default(),
+        DisplayRadius(SpaceObject::Sun.scaled_radius(&db, &exaggeration)),
+        MeshRadius(SpaceObject::Sun.scaled_radius(&db, &exaggeration)),
+        VisualExtent::of(SpaceObject::Sun, &db),
+        OrbitState::at(Vec3::ZERO),
+        // the light is inside the Sun, so it'd shadow everything
+        NotShadowCaster,
+        SunMaterials {
+            textured: sun_material,
+            flat: materials.add(flat_material(SpaceObject::Sun)),
+        },
+    ));
+    sun.with_children(|children| {
         children.spawn(PointLightBundle {
             point_light: PointLight {
                 color: Color::rgb_linear(250.0, 250.0, 250.0),
                 intensity: 100_000.0,
                 range: 100_000.0,
+                shadows_enabled: shadows.0,
                 ..default()
             },
             transform: Transform::from_xyz(0.0, 0.0, 0.0),
             ..default()
         });
     });
+    if start.0 == Some(SpaceObject::Sun) {
+        sun.insert(CurrentObject);
+    }
+}
 
-    object!(Mercury);
-    object!(Venus);
-
-    object!(Earth);
-    object!(EarthMoon);
-
-    object!(Mars);
-    object!(Phobos, Color::GRAY);
-    object!(Deimos, Color::GRAY);
-
-    object!(Jupiter);
-    object!(Io, Color::YELLOW_GREEN);
-    object!(Metis, Color::PINK);
-    object!(Adrastea, Color::GRAY);
-    object!(Amalthea, Color::GRAY);
-    object!(Thebe, Color::GRAY);
-
-    object!(Saturn);
-    object!(Enceladus, Color::WHITE);
-    object!(Mimas, Color::GRAY);
-    object!(Tethys, Color::WHITE);
-    object!(Dione, Color::WHITE);
-    object!(Rhea, Color::BISQUE);
-    object!(Titan, Color::ORANGE);
-
-    object!(Uranus);
-    object!(Miranda, Color::GRAY);
-    object!(Ariel, Color::WHITE);
-    object!(Umbriel, Color::GRAY);
-    object!(Titania, Color::BLUE);
-    object!(Oberon, Color::BLUE);
-
-    object!(Neptune);
-    object!(Triton, Color::PINK);
-    object!(Nereid, Color::GRAY);
-    object!(Proteus, Color::GRAY);
-    object!(Larissa, Color::GRAY);
-    object!(Halimede, Color::GRAY);
-
-    object!(Pluto);
-    object!(Charon, Color::GRAY);
-    object!(Nix, Color::GRAY);
-    object!(Hydra, Color::GRAY);
-    object!(Kerberos, Color::GRAY);
-    object!(Styx, Color::GRAY);
-}
-
-fn planet_orbit(time: Res<Time>, mut planet_q: Query<(&mut Transform, &SpaceObject)>) {
-    let mut main_planets = Vec::with_capacity(8);
-
-    for (mut transform, planet) in planet_q
-        .iter_mut()
-        .filter(|(_, p)| p.orbits() == SpaceObject::Sun)
-    {
-        transform.translate_around(
-            Vec3::ZERO,
-            Quat::from_rotation_y(planet.orbital_velocity() * time.delta_seconds()),
-        );
-        main_planets.push((*transform, *planet));
-    }
-    for (mut transform, planet, orbit) in planet_q
-        .iter_mut()
-        .filter(|(_, &o)| o != SpaceObject::Sun)
-        .filter_map(|(t, p)| {
-            Some((
-                *t,
-                p,
-                main_planets
-                    .iter()
-                    .find_map(|(_, orbit)| if p.orbits() == *orbit { Some(*t) } else { None })?,
-            ))
-        })
-    {
-        transform.translate_around(
-            orbit.translation,
-            Quat::from_rotation_y(planet.orbital_velocity() * time.delta_seconds()),
-        );
+/// A solid, unlit material in the color of `obj`'s category.
+fn flat_material(obj: SpaceObject) -> StandardMaterial {
+    StandardMaterial {
+        base_color: obj.category().color(),
+        unlit: true,
+        ..default()
     }
 }
 
-fn escape(
+fn apply_render_style(
     mut commands: Commands,
-    obj: Query<Entity, With<CurrentObject>>,
-    kbd: ResMut<Input<KeyCode>>,
+    style: Res<RenderStyle>,
+    mut objs: Query<(&ObjectMaterials, &mut Handle<StandardMaterial>)>,
+    sun: Query<(Entity, &SunMaterials)>,
 ) {
-    if kbd.just_pressed(KeyCode::Escape) {
-        info!("Escape pressed");
+    if !style.is_changed() || style.is_added() {
+        return;
+    }
+
+    for (materials, mut material) in objs.iter_mut() {
+        *material = match *style {
+            RenderStyle::Textured => materials.textured.clone(),
+            RenderStyle::Flat => materials.flat.clone(),
+        };
+    }
 
-        if let Ok(planet) = obj.get_single() {
-            commands.entity(planet).remove::<CurrentObject>();
+    for (entity, materials) in sun.iter() {
+        let mut entity = commands.entity(entity);
+        match *style {
+            RenderStyle::Textured => {
+                entity
+                    .remove::<Handle<StandardMaterial>>()
+                    .insert(materials.textured.clone());
+            }
+            RenderStyle::Flat => {
+                entity
+                    .remove::<Handle<SunMaterial>>()
+                    .insert(materials.flat.clone());
+            }
         }
     }
 }
 
-fn reset_camera(
-    no_planet: Query<Entity, With<CurrentObject>>,
-    mut cam: Query<&mut Transform, With<MainCamera>>,
+// meshes are built at their true scaled size, so grow the ones under the floor with their scale
+fn apply_display_size(
+    min_radius: Res<MinVisibleRadius>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    mut objs: Query<(
+        &SpaceObject,
+        &mut Transform,
+        &mut DisplayRadius,
+        &MeshRadius,
+        ChangeTrackers<DisplayRadius>,
+    )>,
 ) {
-    if no_planet.is_empty() {
-        *cam.single_mut() = Transform::from_xyz(
-            DEFAULT_CAMERA_POSITION.x,
-            DEFAULT_CAMERA_POSITION.y,
-            DEFAULT_CAMERA_POSITION.z,
-        )
-        .looking_at(Vec3::ZERO, Vec3::Y);
+    let resized = min_radius.is_changed() || db.is_changed() || exaggeration.is_changed();
+
+    for (obj, mut transform, mut display_radius, mesh_radius, tracker) in objs.iter_mut() {
+        // objects spawned while loading still need sizing once
+        if !resized && !tracker.is_added() {
+            continue;
+        }
+        display_radius.0 = obj.scaled_radius(&db, &exaggeration).max(min_radius.0);
+        transform.scale = Vec3::splat(display_radius.0 / mesh_radius.0);
     }
 }
 
-// when a planet is selected, show information about it, zoom in on it, and change the camera's orbit
-fn object_selected(
+/// How many objects to spawn each frame while loading, so there's no one long hitch.
+const OBJECTS_PER_FRAME: usize = 4;
+
+// spawn the next few objects, and start the simulation once they're all in
+#[allow(clippy::too_many_arguments)]
+fn spawn_objects(
     mut commands: Commands,
-    mut events: EventReader<PickingEvent>,
-    current_planet: Query<Entity, With<CurrentObject>>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    palette: Res<MoonPalette>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    textures: Res<TextureFiles>,
+    start: Res<StartObject>,
+    mut queue: ResMut<SpawnQueue>,
+    mut state: ResMut<State<AppState>>,
 ) {
-    for event in events.iter() {
-        if let PickingEvent::Selection(SelectionEvent::JustSelected(entity)) = event {
-            info!(?entity, "Selected planet");
-            if let Ok(planet) = current_planet.get_single() {
-                commands.entity(planet).remove::<CurrentObject>();
-            }
-            commands.entity(*entity).insert(CurrentObject);
+    for _ in 0..OBJECTS_PER_FRAME {
+        let Some(obj) = queue.pending.pop() else {
+            break;
+        };
+        let mut obj_id = spawn_object(
+            &mut commands,
+            &asset_server,
+            &mut meshes,
+            &mut materials,
+            *palette,
+            &db,
+            &exaggeration,
+            &textures,
+            obj,
+        );
+        if start.0 == Some(obj) {
+            obj_id.insert(CurrentObject);
+        }
+    }
+
+    if queue.pending.is_empty() {
+        if let Err(err) = state.set(AppState::Running) {
+            warn!("couldn't finish loading: {err:?}");
         }
     }
 }
 
-fn lock_to_object(
-    planet: Query<(&SpaceObject, &Transform), With<CurrentObject>>,
-    mut rig: Query<&mut Rig>,
+#[allow(clippy::too_many_arguments)]
+fn spawn_object<'w, 's, 'a>(
+    commands: &'a mut Commands<'w, 's>,
+    asset_server: &AssetServer,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    palette: MoonPalette,
+    db: &BodyDatabase,
+    exaggeration: &ExaggerationSettings,
+    textures: &TextureFiles,
+    obj: SpaceObject,
+) -> EntityCommands<'w, 's, 'a> {
+    let mesh = Mesh::from(shape::UVSphere {
+        radius: obj.scaled_radius(db, exaggeration),
+        sectors: 64,
+        stacks: 64,
+    });
+
+    // objects without a texture get a color from the palette instead
+    let color = palette.color(obj);
+    let texture = color
+        .is_none()
+        .then(|| textures.texture(TexturePack::Photographic, obj, false))
+        .flatten()
+        .map(|path| asset_server.load(path));
+
+    let textured = materials.add(StandardMaterial {
+        base_color: color.unwrap_or(Color::WHITE),
+        base_color_texture: texture,
+        // not reflective
+        reflectance: 0.0,
+        metallic: 0.0,
+        ..default()
+    });
+    let flat = materials.add(flat_material(obj));
+
+    let mut obj_id = commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(mesh),
+            material: textured.clone(),
+            transform: Transform::from_translation(obj.start_position(db, exaggeration))
+                .with_rotation(obj.pole_orientation(db)),
+            ..default()
+        },
+        PickableBundle::default(), // <- Makes the mesh pickable.
+    ));
+    obj_id.insert((
+        obj,
+        Velocity::default(),
+        ObjectMaterials { textured, flat },
+        DisplayRadius(obj.scaled_radius(db, exaggeration)),
+        MeshRadius(obj.scaled_radius(db, exaggeration)),
+        VisualExtent::of(obj, db),
+        OrbitState::at(obj.start_position(db, exaggeration)),
+    ));
+
+    if let Some((inner, outer)) = obj.rings(db) {
+        obj_id.with_children(|children| {
+            children.spawn((
+                PbrBundle {
+                    mesh: meshes.add(rings::ring_mesh(
+                        inner * obj.scaled_radius(db, exaggeration),
+                        outer * obj.scaled_radius(db, exaggeration),
+                        128,
+                    )),
+                    material: materials.add(StandardMaterial {
+                        base_color: if rings::ring_texture(obj).is_some() {
+                            Color::WHITE
+                        } else {
+                            Color::rgb(0.82, 0.74, 0.6)
+                        },
+                        base_color_texture: rings::ring_texture(obj)
+                            .map(|path| asset_server.load(path)),
+                        // the gaps between the rings are see-through
+                        alpha_mode: AlphaMode::Blend,
+                        reflectance: 0.0,
+                        metallic: 0.0,
+                        double_sided: true,
+                        cull_mode: None,
+                        ..default()
+                    }),
+                    // the sphere's poles are along its local Z, so lay the ring flat in its equator
+                    transform: Transform::from_rotation(Quat::from_rotation_x(
+                        90.0_f32.to_radians(),
+                    )),
+                    ..default()
+                },
+                // clicking the rings selects the planet they belong to
+                PickableBundle::default(),
+                rings::Rings,
+            ));
+        });
+    }
+
+    obj_id
+}
+
+fn loading_ui(mut egui_ctx: ResMut<EguiContext>, queue: Res<SpawnQueue>) {
+    let spawned = queue.total - queue.pending.len();
+
+    egui::CentralPanel::default().show(egui_ctx.ctx_mut(), |ui| {
+        ui.vertical_centered(|ui| {
+            ui.add_space(ui.available_height() / 2.0);
+            ui.label(RichText::new("Loading the Solar System").size(20.0));
+            ui.add(
+                egui::ProgressBar::new(spawned as f32 / queue.total as f32)
+                    .desired_width(300.0)
+                    .show_percentage(),
+            );
+        });
+    });
+}
+
+// the windows wait for loading to finish, so they don't cover the progress bar
+fn ui_visible(state: Res<State<AppState>>, show_ui: Res<ShowUi>) -> ShouldRun {
+    if show_ui.0 && *state.current() == AppState::Running {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+fn toggle_ui(
+    kbd: Res<Input<KeyCode>>,
+    mut show_ui: ResMut<ShowUi>,
+    mut hovered_entry: ResMut<HoveredListEntry>,
 ) {
-    if let Ok((planet, transform)) = planet.get_single() {
-        let mut rig = rig.single_mut();
-        rig.driver_mut::<LookAt>().target = transform.transform_2_dolly().position;
-        let mut cam_pos = glam::Vec3::Z * planet.scaled_radius() * 3.0;
+    if kbd.just_pressed(KeyCode::H) {
+        show_ui.0 = !show_ui.0;
+        // the list can't be hovered while it's hidden
+        if hovered_entry.0.is_some() {
+            hovered_entry.0 = None;
+        }
+    }
+}
 
-        if transform.translation.z < 0.0 {
-            cam_pos.z = -cam_pos.z;
+fn apply_shadows(shadows: Res<ShowShadows>, mut lights: Query<&mut PointLight>) {
+    if shadows.is_changed() {
+        for mut light in lights.iter_mut() {
+            light.shadows_enabled = shadows.0;
         }
+    }
+}
 
-        rig.driver_mut::<Position>().position = transform.transform_2_dolly().position + cam_pos;
+// while a text box has focus, keys are typed into it instead of toggling things around the app
+fn release_keys_for_egui(mut egui_ctx: ResMut<EguiContext>, mut kbd: ResMut<Input<KeyCode>>) {
+    if !egui_ctx.ctx_mut().wants_keyboard_input() {
+        return;
+    }
+    let held: Vec<KeyCode> = kbd.get_pressed().copied().collect();
+    for key in held {
+        kbd.reset(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separators_between_thousands() {
+        assert_eq!(with_separators(0.0), "0");
+        assert_eq!(with_separators(999.0), "999");
+        assert_eq!(with_separators(12_742.0), "12,742");
+        assert_eq!(with_separators(1_234_567.0), "1,234,567");
+        assert_eq!(with_separators(-1_234.5), "-1,234.5");
+    }
+
+    #[test]
+    fn scientific_notation_edge_cases() {
+        assert_eq!(scientific_notation(0.0), "0");
+        assert_eq!(scientific_notation(f32::NAN), "NaN");
+        assert_eq!(scientific_notation(f32::INFINITY), "inf");
+        assert_eq!(scientific_notation(5.0), "5x10^0");
+
+        // repeated division leaves the last few digits slightly off
+        let mantissa = |formatted: &str, exp: &str| -> f32 {
+            let (mantissa, rest) = formatted.split_once("x10^").unwrap();
+            assert_eq!(rest, exp, "{formatted}");
+            mantissa.parse().unwrap()
+        };
+        assert!((mantissa(&scientific_notation(-2.5e24), "24") + 2.5).abs() < 1e-4);
+        assert!((mantissa(&scientific_notation(0.02), "-2") - 2.0).abs() < 1e-4);
     }
 }
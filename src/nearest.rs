@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    scientific_notation,
+    selection::Selection,
+    space::{BodyDatabase, ExaggerationSettings, SpaceObject},
+    ui_visible, MainCamera,
+};
+
+/// Pointing out which object the camera is closest to, and selecting it with `N`.
+pub struct NearestPlugin;
+
+impl Plugin for NearestPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(select_nearest)
+            .add_system(nearest_object_hud.with_run_criteria(ui_visible));
+    }
+}
+
+// whichever object is closest to the camera, by the distance between their centers
+pub fn nearest_object(
+    camera: &Query<&GlobalTransform, (With<MainCamera>, With<Camera>)>,
+    objs: &Query<(&SpaceObject, &GlobalTransform)>,
+) -> Option<(SpaceObject, f32)> {
+    let camera = camera.get_single().ok()?.translation();
+
+    objs.iter()
+        .map(|(obj, transform)| (*obj, transform.translation().distance(camera)))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+fn select_nearest(
+    mut selection: Selection,
+    kbd: Res<Input<KeyCode>>,
+    camera: Query<&GlobalTransform, (With<MainCamera>, With<Camera>)>,
+    objs: Query<(&SpaceObject, &GlobalTransform)>,
+) {
+    if !kbd.just_pressed(KeyCode::N) {
+        return;
+    }
+    if let Some((nearest, _)) = nearest_object(&camera, &objs) {
+        selection.select(nearest);
+    }
+}
+
+// show which object the camera is closest to, which `N` selects
+fn nearest_object_hud(
+    mut egui_ctx: ResMut<EguiContext>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    camera: Query<&GlobalTransform, (With<MainCamera>, With<Camera>)>,
+    objs: Query<(&SpaceObject, &GlobalTransform)>,
+) {
+    let Some((nearest, distance)) = nearest_object(&camera, &objs) else {
+        return;
+    };
+
+    let km = SpaceObject::unscaled_km(distance, &exaggeration);
+    egui::Area::new("nearest object")
+        .anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(16.0, -16.0))
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.label(format!(
+                "Nearest: {} ({} km away), press N to select it",
+                nearest.name(&db),
+                scientific_notation(km.max(1.0))
+            ));
+        });
+}
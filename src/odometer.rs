@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, RichText},
+    EguiContext,
+};
+
+use crate::{
+    lock::{track_velocity, RideAlong, Velocity},
+    scientific_notation,
+    selection::CurrentObject,
+    space::{BodyDatabase, ExaggerationSettings, SpaceObject},
+    stats, ui_visible,
+};
+
+/// Adding up how far the object being ridden along with travels, and showing it.
+pub struct OdometerPlugin;
+
+impl Plugin for OdometerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Odometer>()
+            .add_system(track_odometer.after(track_velocity))
+            .add_system(odometer_hud.with_run_criteria(ui_visible));
+    }
+}
+
+/// How far the object being ridden along with has gone since the ride started, in kilometers.
+#[derive(Resource, Default)]
+pub struct Odometer {
+    following: Option<SpaceObject>,
+    km: f32,
+}
+
+// add up how far the ridden object moves, starting over whenever the ride changes
+fn track_odometer(
+    time: Res<Time>,
+    ride_along: Res<RideAlong>,
+    exaggeration: Res<ExaggerationSettings>,
+    current: Query<(&SpaceObject, &Velocity), With<CurrentObject>>,
+    mut odometer: ResMut<Odometer>,
+) {
+    let riding = current
+        .get_single()
+        .ok()
+        .filter(|(obj, _)| ride_along.0 == Some(**obj));
+
+    let following = riding.map(|(obj, _)| *obj);
+    if odometer.following != following {
+        *odometer = Odometer { following, km: 0.0 };
+    }
+
+    if let Some((_, velocity)) = riding {
+        let distance = velocity.linear.length() * time.delta_seconds();
+        odometer.km += SpaceObject::unscaled_km(distance, &exaggeration);
+    }
+}
+
+fn odometer_hud(
+    mut egui_ctx: ResMut<EguiContext>,
+    odometer: Res<Odometer>,
+    strip: Res<stats::ShowStatsStrip>,
+    db: Res<BodyDatabase>,
+) {
+    let Some(obj) = odometer.following else {
+        return;
+    };
+
+    // sit above the stats strip, if it's there
+    let bottom = if strip.0 { -40.0 } else { -16.0 };
+    egui::Area::new("odometer")
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, bottom))
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.label(
+                RichText::new(format!(
+                    "{} has moved {} km while you watched",
+                    obj.name(&db),
+                    scientific_notation(odometer.km.max(1.0))
+                ))
+                .size(16.0),
+            );
+        });
+}
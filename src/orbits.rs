@@ -0,0 +1,156 @@
+use bevy::{pbr::NotShadowCaster, prelude::*, render::render_resource::PrimitiveTopology};
+
+use crate::{
+    palette::MoonPalette,
+    space::{BodyDatabase, ExaggerationSettings, SpaceObject},
+};
+
+/// How the paths of the planets and moons are drawn.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OrbitDisplay {
+    Hidden,
+    /// Single pixel lines, which are cheap but easy to lose on big or bright screens.
+    #[default]
+    Lines,
+    /// Thin tubes, which stay visible from far away and on projectors.
+    Tubes,
+}
+
+/// Marks the mesh drawing an object's orbit.
+#[derive(Component)]
+pub struct OrbitPath {
+    /// What the orbit goes around, which the path moves along with.
+    around: SpaceObject,
+    /// Where the mesh sits from the middle of what it goes around.
+    offset: Vec3,
+}
+
+/// How many line segments make up an orbit.
+const LINE_SEGMENTS: usize = 256;
+
+/// How thick a tube is, as a fraction of its orbit's radius.
+const TUBE_THICKNESS: f32 = 0.003;
+
+/// The path `obj` follows around what it orbits, traced through a whole orbit.
+fn line_mesh(obj: SpaceObject, db: &BodyDatabase, exaggeration: &ExaggerationSettings) -> Mesh {
+    let positions: Vec<[f32; 3]> = (0..=LINE_SEGMENTS)
+        .map(|i| {
+            let mean_anomaly = std::f32::consts::TAU * i as f32 / LINE_SEGMENTS as f32;
+            obj.orbit_position(mean_anomaly, db, exaggeration)
+                .to_array()
+        })
+        .collect();
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineStrip);
+    // the PBR pipeline wants these even though the lines are unlit
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        vec![[0.0, 1.0, 0.0]; positions.len()],
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; positions.len()]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh
+}
+
+/// A distinct color for each planet, going around the color wheel from the Sun outwards.
+fn orbit_color(index: usize, count: usize) -> Color {
+    Color::hsl(360.0 * index as f32 / count as f32, 0.6, 0.6)
+}
+
+// redraw every orbit whenever the style, the scale of the scene or the moon colors change
+#[allow(clippy::too_many_arguments)]
+pub fn draw_orbits(
+    mut commands: Commands,
+    display: Res<OrbitDisplay>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    palette: Res<MoonPalette>,
+    paths: Query<Entity, With<OrbitPath>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !display.is_changed()
+        && !db.is_changed()
+        && !exaggeration.is_changed()
+        && !palette.is_changed()
+    {
+        return;
+    }
+
+    for entity in paths.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if *display == OrbitDisplay::Hidden {
+        return;
+    }
+
+    let planets: Vec<_> = SpaceObject::Sun.moons(&db).collect();
+    let moons = planets.iter().flat_map(|planet| planet.moons(&db));
+    let colored = planets
+        .iter()
+        .enumerate()
+        .map(|(i, obj)| (*obj, orbit_color(i, planets.len())))
+        .chain(moons.map(|moon| {
+            let color = palette.color(moon).unwrap_or(moon.category().color());
+            (moon, color)
+        }));
+
+    for (obj, color) in colored {
+        let orbit = obj.orbital_elements(&db, &exaggeration);
+        let (mesh, transform) = match *display {
+            OrbitDisplay::Hidden | OrbitDisplay::Lines => {
+                (line_mesh(obj, &db, &exaggeration), Transform::IDENTITY)
+            }
+            // a torus can only be squashed into an ellipse, which is close enough for a moon's
+            // slightly pushed out path
+            OrbitDisplay::Tubes => (
+                Mesh::from(shape::Torus {
+                    radius: orbit.semi_major_axis,
+                    ring_radius: orbit.semi_major_axis * TUBE_THICKNESS,
+                    subdivisions_segments: LINE_SEGMENTS,
+                    subdivisions_sides: 8,
+                }),
+                // the Sun is at a focus, not the middle
+                Transform {
+                    translation: orbit.center(),
+                    rotation: orbit.plane,
+                    scale: Vec3::new(1.0, 1.0, orbit.semi_minor_axis() / orbit.semi_major_axis),
+                },
+            ),
+        };
+
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(mesh),
+                material: materials.add(StandardMaterial {
+                    base_color: color,
+                    unlit: true,
+                    ..default()
+                }),
+                transform,
+                ..default()
+            },
+            NotShadowCaster,
+            OrbitPath {
+                around: obj.orbits(&db),
+                offset: transform.translation,
+            },
+        ));
+    }
+}
+
+// carry the moons' paths along with their planets. They aren't children of the planet, since
+// they'd spin with it
+pub fn follow_orbited_objects(
+    objs: Query<(&SpaceObject, &Transform), Without<OrbitPath>>,
+    mut paths: Query<(&OrbitPath, &mut Transform)>,
+) {
+    for (path, mut transform) in paths.iter_mut() {
+        if path.around == SpaceObject::Sun {
+            continue;
+        }
+        if let Some((_, around)) = objs.iter().find(|(obj, _)| **obj == path.around) {
+            transform.translation = around.translation + path.offset;
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use bevy::{input::mouse::MouseMotion, prelude::*};
+use bevy_dolly::{dolly::glam, prelude::*};
+use bevy_egui::EguiContext;
+
+use crate::{
+    ladder,
+    lock::lock_to_object,
+    selection::{escape, CurrentObject},
+    zoom::{keyboard_zoom, pinch_zoom, CameraZoom},
+    CameraOrbit, MainCamera,
+};
+
+/// The view of the whole system while nothing is selected, which can be dragged around.
+pub struct OverviewPlugin;
+
+impl Plugin for OverviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraPan>()
+            .add_system(pan_overview.after(escape))
+            .add_system(
+                reset_camera
+                    .after(escape)
+                    .after(pan_overview)
+                    .after(lock_to_object)
+                    .after(keyboard_zoom)
+                    .after(pinch_zoom),
+            );
+    }
+}
+
+/// Where the camera looks at the whole system from, before it's zoomed, panned or turned.
+pub const DEFAULT_CAMERA_POSITION: glam::Vec3 = glam::Vec3::new(0.0, 100.0, 100_000.0);
+
+/// How far the overview has been dragged across the ecliptic from the Sun, in scene units.
+#[derive(Resource, Default)]
+pub struct CameraPan(pub Vec3);
+
+/// How far the overview pans per pixel dragged, as a fraction of the camera's distance.
+const PAN_SPEED: f32 = 0.0015;
+
+// dragging with the right or middle mouse button slides the overview across the system
+pub fn pan_overview(
+    mut egui_ctx: ResMut<EguiContext>,
+    mouse: Res<Input<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    current: Query<(), With<CurrentObject>>,
+    cam: Query<&Transform, With<MainCamera>>,
+    mut pan: ResMut<CameraPan>,
+) {
+    let dragged = motion
+        .iter()
+        .fold(Vec2::ZERO, |dragged, event| dragged + event.delta);
+
+    // selecting something starts the next overview back over the Sun
+    if !current.is_empty() {
+        if pan.0 != Vec3::ZERO {
+            pan.0 = Vec3::ZERO;
+        }
+        return;
+    }
+    if dragged == Vec2::ZERO
+        || !mouse.any_pressed([MouseButton::Right, MouseButton::Middle])
+        || egui_ctx.ctx_mut().is_pointer_over_area()
+    {
+        return;
+    }
+    let Ok(cam) = cam.get_single() else {
+        return;
+    };
+
+    // slide along the ecliptic, so the camera keeps its height whichever way it faces
+    let flatten = |direction: Vec3| Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
+    let distance = cam.translation.distance(pan.0);
+    pan.0 += (flatten(cam.forward()) * dragged.y - flatten(cam.right()) * dragged.x)
+        * distance
+        * PAN_SPEED;
+}
+
+// with nothing selected, steer the rig back to the overview, so the camera glides there with the
+// same smoothing it follows objects with instead of snapping. The targets are set fresh every
+// frame, so pressing `Esc` again never stacks anything up
+pub fn reset_camera(
+    no_planet: Query<Entity, With<CurrentObject>>,
+    mut rig: Query<&mut Rig>,
+    orbit: Res<CameraOrbit>,
+    zoom: Res<CameraZoom>,
+    pan: Res<CameraPan>,
+    ladder: Res<ladder::DistanceLadder>,
+) {
+    // the ladder moves the camera itself
+    if !no_planet.is_empty() || ladder.is_running() {
+        return;
+    }
+
+    let position = glam::Quat::from_rotation_y(orbit.yaw)
+        * DEFAULT_CAMERA_POSITION
+        * (zoom.0 / CameraZoom::DEFAULT);
+    let pan = glam::Vec3::new(pan.0.x, pan.0.y, pan.0.z);
+
+    let Ok(mut rig) = rig.get_single_mut() else {
+        debug!("no camera rig to reset");
+        return;
+    };
+    rig.driver_mut::<Position>().position = pan + position;
+    rig.driver_mut::<LookAt>().target = pan;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_camera_doesnt_panic() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<CameraOrbit>()
+            .init_resource::<CameraZoom>()
+            .init_resource::<CameraPan>()
+            .init_resource::<ladder::DistanceLadder>()
+            .add_system(reset_camera);
+
+        app.update();
+    }
+}
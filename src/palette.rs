@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+
+use crate::{space::SpaceObject, ObjectMaterials};
+
+/// How to color the objects that don't have a texture, which is most of the moons.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MoonPalette {
+    /// Bright, distinct colors that make the moons easy to tell apart.
+    #[default]
+    Artistic,
+    /// Grays and tans based on what each moon's surface actually looks like.
+    Realistic,
+}
+
+impl MoonPalette {
+    /// The color for an object, or [`None`] if it has a texture instead.
+    pub fn color(self, obj: SpaceObject) -> Option<Color> {
+        match self {
+            Self::Artistic => artistic_color(obj),
+            Self::Realistic => realistic_color(obj),
+        }
+    }
+}
+
+fn artistic_color(obj: SpaceObject) -> Option<Color> {
+    use SpaceObject::*;
+
+    Some(match obj {
+        Io => Color::YELLOW_GREEN,
+        Metis | Triton => Color::PINK,
+        Enceladus | Tethys | Dione | Ariel => Color::WHITE,
+        Rhea => Color::BISQUE,
+        Titan => Color::ORANGE,
+        Titania | Oberon => Color::BLUE,
+        Phobos | Deimos | Adrastea | Amalthea | Thebe | Mimas | Miranda | Umbriel | Nereid
+        | Proteus | Larissa | Halimede | Charon | Nix | Hydra | Kerberos | Styx => Color::GRAY,
+        _ => return None,
+    })
+}
+
+// roughly each moon's albedo, tinted where it has a noticeable color
+fn realistic_color(obj: SpaceObject) -> Option<Color> {
+    use SpaceObject::*;
+
+    Some(match obj {
+        Phobos | Deimos => Color::rgb(0.25, 0.23, 0.21),
+        Io => Color::rgb(0.85, 0.8, 0.45),
+        Metis | Adrastea | Thebe => Color::rgb(0.35, 0.25, 0.2),
+        Amalthea => Color::rgb(0.45, 0.28, 0.22),
+        Enceladus => Color::rgb(0.98, 0.98, 1.0),
+        Mimas => Color::rgb(0.75, 0.75, 0.75),
+        Tethys => Color::rgb(0.9, 0.9, 0.9),
+        Dione => Color::rgb(0.85, 0.85, 0.85),
+        Rhea => Color::rgb(0.8, 0.78, 0.75),
+        Titan => Color::rgb(0.85, 0.6, 0.3),
+        Miranda => Color::rgb(0.6, 0.6, 0.6),
+        Ariel => Color::rgb(0.65, 0.65, 0.65),
+        Umbriel => Color::rgb(0.35, 0.35, 0.35),
+        Titania => Color::rgb(0.55, 0.52, 0.5),
+        Oberon => Color::rgb(0.5, 0.46, 0.44),
+        Triton => Color::rgb(0.85, 0.8, 0.78),
+        Nereid => Color::rgb(0.5, 0.5, 0.5),
+        Proteus | Larissa => Color::rgb(0.3, 0.3, 0.3),
+        Halimede => Color::rgb(0.35, 0.35, 0.35),
+        Charon => Color::rgb(0.45, 0.43, 0.42),
+        Nix | Hydra => Color::rgb(0.8, 0.8, 0.8),
+        Kerberos => Color::rgb(0.6, 0.6, 0.6),
+        Styx => Color::rgb(0.7, 0.7, 0.7),
+        _ => return None,
+    })
+}
+
+pub fn apply_moon_palette(
+    palette: Res<MoonPalette>,
+    objs: Query<(&SpaceObject, &ObjectMaterials)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !palette.is_changed() || palette.is_added() {
+        return;
+    }
+
+    for (obj, obj_materials) in objs.iter() {
+        let Some(color) = palette.color(*obj) else {
+            continue;
+        };
+        if let Some(material) = materials.get_mut(&obj_materials.textured) {
+            material.base_color = color;
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::marker::PhantomData;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use enum_iterator::Sequence;
+
+use crate::{
+    orbits::OrbitDisplay, palette::MoonPalette, space::ExaggerationSettings, MinVisibleRadius,
+};
+
+/// A whole set of realism settings picked at once, from the "Preset" dropdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Sequence)]
+pub enum RealismPreset {
+    /// Big, bright and easy to find: the way the scene starts out.
+    Education,
+    /// True sizes and distances, lit only by the Sun, the way it would look from out there.
+    Realistic,
+}
+
+impl RealismPreset {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Education => "Education",
+            Self::Realistic => "Realistic",
+        }
+    }
+
+    fn exaggeration(self) -> ExaggerationSettings {
+        match self {
+            Self::Education => ExaggerationSettings::DIAGRAM,
+            Self::Realistic => ExaggerationSettings::REALISTIC,
+        }
+    }
+
+    /// How bright the stars light up the night side.
+    fn ambient_brightness(self) -> f32 {
+        match self {
+            Self::Education => 0.5,
+            Self::Realistic => 0.05,
+        }
+    }
+
+    fn orbits(self) -> OrbitDisplay {
+        match self {
+            Self::Education => OrbitDisplay::Lines,
+            Self::Realistic => OrbitDisplay::Hidden,
+        }
+    }
+
+    fn palette(self) -> MoonPalette {
+        match self {
+            Self::Education => MoonPalette::Artistic,
+            Self::Realistic => MoonPalette::Realistic,
+        }
+    }
+
+    fn min_visible_radius(self) -> f32 {
+        match self {
+            Self::Education => MinVisibleRadius::default().0,
+            Self::Realistic => 0.0,
+        }
+    }
+}
+
+/// Every resource a [`RealismPreset`] sets.
+#[derive(SystemParam)]
+pub struct RealismSettings<'w, 's> {
+    pub exaggeration: ResMut<'w, ExaggerationSettings>,
+    pub ambient: ResMut<'w, AmbientLight>,
+    pub orbits: ResMut<'w, OrbitDisplay>,
+    pub palette: ResMut<'w, MoonPalette>,
+    pub min_radius: ResMut<'w, MinVisibleRadius>,
+    #[system_param(ignore)]
+    _marker: PhantomData<&'s ()>,
+}
+
+impl RealismSettings<'_, '_> {
+    /// The preset everything is currently set to, or [`None`] if something's been changed since.
+    pub fn preset(&self) -> Option<RealismPreset> {
+        enum_iterator::all::<RealismPreset>().find(|&preset| {
+            *self.exaggeration == preset.exaggeration()
+                && self.ambient.brightness == preset.ambient_brightness()
+                && *self.orbits == preset.orbits()
+                && *self.palette == preset.palette()
+                && self.min_radius.0 == preset.min_visible_radius()
+        })
+    }
+
+    pub fn apply(&mut self, preset: RealismPreset) {
+        *self.exaggeration = preset.exaggeration();
+        self.ambient.brightness = preset.ambient_brightness();
+        *self.orbits = preset.orbits();
+        *self.palette = preset.palette();
+        self.min_radius.0 = preset.min_visible_radius();
+    }
+}
@@ -0,0 +1,135 @@
+use bevy::{
+    input::{gamepad::GamepadEvent, mouse::MouseWheel},
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+use crate::{
+    gamepad::gamepad_input,
+    lock::lock_to_object,
+    selection::CurrentObject,
+    space::{BodyDatabase, SpaceObject},
+    CameraOrbit,
+};
+
+/// Swinging the camera through a ringed planet's ring plane on request, to show how the rings
+/// seem to vanish edge-on.
+pub struct RingTiltPlugin;
+
+impl Plugin for RingTiltPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RingTiltDemo>()
+            .add_system(ring_tilt_demo.after(gamepad_input).before(lock_to_object));
+    }
+}
+
+/// Marks the ring mesh spawned as a child of a ringed object.
+#[derive(Component)]
+pub struct Rings;
+
+/// The image for `obj`'s rings, which runs from their inner edge to their outer edge.
+/// Rings without one are drawn in a plain color.
+pub fn ring_texture(obj: SpaceObject) -> Option<&'static str> {
+    match obj {
+        SpaceObject::Saturn => Some("saturn_ring.png"),
+        _ => None,
+    }
+}
+
+/// A flat annulus in the XZ plane facing +Y, like a planet's rings.
+/// The texture's U coordinate runs from the inner edge to the outer edge.
+pub fn ring_mesh(inner_radius: f32, outer_radius: f32, segments: u32) -> Mesh {
+    let mut positions = Vec::with_capacity(2 * (segments as usize + 1));
+    let mut uvs = Vec::with_capacity(positions.capacity());
+
+    for i in 0..=segments {
+        let angle = std::f32::consts::TAU * i as f32 / segments as f32;
+        let (sin, cos) = angle.sin_cos();
+        let v = i as f32 / segments as f32;
+
+        positions.push([cos * inner_radius, 0.0, sin * inner_radius]);
+        uvs.push([0.0, v]);
+        positions.push([cos * outer_radius, 0.0, sin * outer_radius]);
+        uvs.push([1.0, v]);
+    }
+
+    let indices = (0..segments)
+        .flat_map(|i| {
+            let (inner, outer) = (2 * i, 2 * i + 1);
+            let (next_inner, next_outer) = (inner + 2, outer + 2);
+            [inner, next_inner, outer, outer, next_inner, next_outer]
+        })
+        .collect();
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        vec![[0.0, 1.0, 0.0]; positions.len()],
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+/// How far into the ring tilt demonstration the camera is, in seconds, if it's running.
+#[derive(Resource, Default)]
+pub struct RingTiltDemo(pub Option<f32>);
+
+/// How long the ring tilt demonstration takes, from edge-on to fully open and back.
+const RING_TILT_DEMO_DURATION: f32 = 20.0;
+
+/// The most Saturn's rings ever tilt towards the Sun (and Earth), in degrees.
+const MAX_RING_TILT: f32 = 26.7;
+
+// swing the camera through the ring plane, from edge-on to as open as the rings ever get and back
+#[allow(clippy::too_many_arguments)]
+pub fn ring_tilt_demo(
+    time: Res<Time>,
+    kbd: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    db: Res<BodyDatabase>,
+    mut wheel: EventReader<MouseWheel>,
+    mut gamepad: EventReader<GamepadEvent>,
+    touches: Res<Touches>,
+    current: Query<(&SpaceObject, &Transform), With<CurrentObject>>,
+    mut orbit: ResMut<CameraOrbit>,
+    mut demo: ResMut<RingTiltDemo>,
+) {
+    let interrupted = wheel.iter().count() + gamepad.iter().count() > 0
+        || kbd.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || touches.iter_just_pressed().next().is_some();
+
+    let Some(elapsed) = demo.0 else {
+        return;
+    };
+    let Ok((obj, transform)) = current.get_single() else {
+        demo.0 = None;
+        return;
+    };
+    if interrupted || !obj.has_rings(&db) {
+        demo.0 = None;
+        return;
+    }
+    if elapsed > RING_TILT_DEMO_DURATION {
+        // back to the usual view from above
+        orbit.pitch = 0.0;
+        demo.0 = None;
+        return;
+    }
+
+    // `lock_to_object` already looks down on rings from this high up
+    let base = 0.5_f32.atan();
+    let open = MAX_RING_TILT.to_radians()
+        * (elapsed / RING_TILT_DEMO_DURATION * std::f32::consts::PI).sin();
+    // the camera flips to the other side of objects behind the Sun, which flips the pitch too
+    let side = if transform.translation.z < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    orbit.pitch = side * (open - base);
+
+    demo.0 = Some(elapsed + time.delta_seconds());
+}
@@ -0,0 +1,292 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::EguiContext;
+use bevy_mod_picking::{PickingCamera, PickingEvent, SelectionEvent};
+use enum_iterator::Sequence;
+
+use crate::{overview::CameraPan, space::SpaceObject};
+
+/// Picking objects by clicking them, cycling through them from the keyboard, and letting go of
+/// them again with `Esc` or a click on empty space.
+pub struct SelectionPlugin;
+
+impl Plugin for SelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(object_selected)
+            .add_system(click_empty_space.after(object_selected))
+            .add_system(escape.after(object_selected))
+            .add_system(keyboard_cycle.after(object_selected))
+            // the selection from this frame has been applied by now
+            .add_system_to_stage(CoreStage::PostUpdate, enforce_single_selection);
+    }
+}
+
+/// Marks the selected object, which the camera locks on to and the info window is about.
+#[derive(Component)]
+pub struct CurrentObject;
+
+/// Changes which object is selected, by moving [`CurrentObject`] between them.
+#[derive(SystemParam)]
+pub struct Selection<'w, 's> {
+    commands: Commands<'w, 's>,
+    current: Query<'w, 's, Entity, With<CurrentObject>>,
+    objs: Query<'w, 's, (Entity, &'static SpaceObject)>,
+}
+
+impl<'w, 's> Selection<'w, 's> {
+    /// Moves [`CurrentObject`] onto the entity for `obj`, removing it from whatever had it before.
+    pub fn select(&mut self, obj: SpaceObject) {
+        for (entity, other) in self.objs.iter() {
+            if *other == obj {
+                self.commands.entity(entity).insert(CurrentObject);
+            } else if self.current.contains(entity) {
+                self.commands.entity(entity).remove::<CurrentObject>();
+            }
+        }
+    }
+
+    /// Removes [`CurrentObject`] from everything that has it, which should only ever be one
+    /// entity.
+    pub fn deselect(&mut self) {
+        for entity in self.current.iter() {
+            self.commands.entity(entity).remove::<CurrentObject>();
+        }
+    }
+}
+
+// everything that reads the selection expects one object at most, so if a bug ever selects
+// more, keep the newest and drop the rest rather than showing nothing
+fn enforce_single_selection(
+    mut commands: Commands,
+    current: Query<(Entity, ChangeTrackers<CurrentObject>)>,
+) {
+    if current.iter().count() <= 1 {
+        return;
+    }
+
+    let keep = current
+        .iter()
+        .find(|(_, tracker)| tracker.is_added())
+        .or_else(|| current.iter().next())
+        .map(|(entity, _)| entity);
+    warn!("more than one object was selected, keeping {keep:?}");
+    for (entity, _) in current.iter().filter(|(entity, _)| Some(*entity) != keep) {
+        commands.entity(entity).remove::<CurrentObject>();
+    }
+}
+
+/// The object after (or before) `current` in declaration order, wrapping around at the ends.
+/// Starts at the Sun when nothing is selected.
+pub fn cycle_object(current: Option<SpaceObject>, forward: bool) -> SpaceObject {
+    let Some(current) = current else {
+        return SpaceObject::Sun;
+    };
+
+    if forward {
+        current
+            .next()
+            .unwrap_or_else(|| SpaceObject::first().unwrap())
+    } else {
+        current
+            .previous()
+            .unwrap_or_else(|| SpaceObject::last().unwrap())
+    }
+}
+
+pub fn escape(mut selection: Selection, kbd: ResMut<Input<KeyCode>>, mut pan: ResMut<CameraPan>) {
+    if kbd.just_pressed(KeyCode::Escape) {
+        info!("Escape pressed");
+
+        reset_view(&mut selection, &mut pan);
+    }
+}
+
+/// Lets go of the selected object and brings the view back to the Sun, for `Esc` and the
+/// gamepad's east button.
+pub fn reset_view(selection: &mut Selection, pan: &mut CameraPan) {
+    selection.deselect();
+    pan.0 = Vec3::ZERO;
+}
+
+// the right and left arrows cycle the selected object, like the gamepad's bumpers
+fn keyboard_cycle(
+    mut selection: Selection,
+    kbd: Res<Input<KeyCode>>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+) {
+    let forward = if kbd.just_pressed(KeyCode::Right) {
+        true
+    } else if kbd.just_pressed(KeyCode::Left) {
+        false
+    } else {
+        return;
+    };
+
+    let next = cycle_object(current.get_single().ok().copied(), forward);
+    selection.select(next);
+}
+
+/// The [`SpaceObject`] that `entity` belongs to, which is either on itself or one of its
+/// ancestors (for children like rings and the Sun's light).
+fn owning_object(
+    mut entity: Entity,
+    parents: &Query<&Parent>,
+    objs: &Query<&SpaceObject>,
+) -> Option<SpaceObject> {
+    loop {
+        if let Ok(obj) = objs.get(entity) {
+            return Some(*obj);
+        }
+        entity = parents.get(entity).ok()?.get();
+    }
+}
+
+// when a planet is clicked, select it so the camera zooms in and its information is shown
+pub fn object_selected(
+    mut selection: Selection,
+    mut events: EventReader<PickingEvent>,
+    parents: Query<&Parent>,
+    objs: Query<&SpaceObject>,
+) {
+    for event in events.iter() {
+        if let PickingEvent::Selection(SelectionEvent::JustSelected(hit)) = event {
+            let Some(obj) = owning_object(*hit, &parents, &objs) else {
+                debug!(?hit, "picked something that isn't part of an object");
+                continue;
+            };
+            info!("Selected {obj:?}");
+            selection.select(obj);
+        }
+    }
+}
+
+// clicking on empty space deselects, like `Esc` does. this checks the ray itself rather than
+// waiting for a deselection event, since objects picked from the UI were never selected by the
+// picking plugin
+fn click_empty_space(
+    mut selection: Selection,
+    mut egui_ctx: ResMut<EguiContext>,
+    mouse: Res<Input<MouseButton>>,
+    picking_cams: Query<&PickingCamera>,
+) {
+    let over_ui = egui_ctx.ctx_mut().is_pointer_over_area();
+    let missed = picking_cams
+        .iter()
+        .all(|cam| cam.get_nearest_intersection().is_none());
+    if mouse.just_pressed(MouseButton::Left) && !over_ui && missed && !selection.current.is_empty()
+    {
+        info!("Clicked empty space");
+        selection.deselect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+
+    use super::*;
+    use crate::{harness, simulation::SIMULATION_STEP};
+
+    #[test]
+    fn children_belong_to_their_object() {
+        let mut world = World::new();
+        let mut ring = None;
+        let mut grandchild = None;
+        world.spawn(SpaceObject::Saturn).with_children(|saturn| {
+            ring = Some(
+                saturn
+                    .spawn(Transform::default())
+                    .with_children(|ring| {
+                        grandchild = Some(ring.spawn(Transform::default()).id());
+                    })
+                    .id(),
+            );
+        });
+        let stray = world.spawn(Transform::default()).id();
+
+        let mut state: SystemState<(Query<&Parent>, Query<&SpaceObject>)> =
+            SystemState::new(&mut world);
+        let (parents, objs) = state.get(&world);
+        for child in [ring.unwrap(), grandchild.unwrap()] {
+            assert_eq!(
+                owning_object(child, &parents, &objs),
+                Some(SpaceObject::Saturn)
+            );
+        }
+        assert_eq!(owning_object(stray, &parents, &objs), None);
+    }
+
+    /// What the test wants selected next frame, if anything. [`None`] deselects.
+    #[derive(Resource, Default)]
+    struct Choice(Option<Option<SpaceObject>>);
+
+    fn choose(mut selection: Selection, mut choice: ResMut<Choice>) {
+        match choice.0.take() {
+            Some(Some(obj)) => selection.select(obj),
+            Some(None) => selection.deselect(),
+            None => {}
+        }
+    }
+
+    fn selected(app: &mut App) -> Vec<SpaceObject> {
+        app.world
+            .query_filtered::<&SpaceObject, With<CurrentObject>>()
+            .iter(&app.world)
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn at_most_one_object_is_selected() {
+        let mut app = App::new();
+        app.init_resource::<Choice>()
+            .add_system(choose)
+            .add_system_to_stage(CoreStage::PostUpdate, enforce_single_selection);
+        for obj in enum_iterator::all::<SpaceObject>() {
+            app.world.spawn(obj);
+        }
+
+        for choice in [
+            Some(SpaceObject::Earth),
+            Some(SpaceObject::Mars),
+            Some(SpaceObject::Mars),
+            None,
+            Some(SpaceObject::Phobos),
+        ] {
+            app.world.resource_mut::<Choice>().0 = Some(choice);
+            app.update();
+            assert_eq!(selected(&mut app), Vec::from_iter(choice));
+        }
+
+        // a bug selecting a second object directly is cleaned up, keeping the newest
+        let saturn = app
+            .world
+            .query::<(Entity, &SpaceObject)>()
+            .iter(&app.world)
+            .find(|(_, obj)| **obj == SpaceObject::Saturn)
+            .map(|(entity, _)| entity)
+            .unwrap();
+        app.world.entity_mut(saturn).insert(CurrentObject);
+        app.update();
+        assert_eq!(selected(&mut app), [SpaceObject::Saturn]);
+    }
+
+    #[test]
+    fn escape_deselects() {
+        let mut app = harness::app();
+        app.add_system(object_selected)
+            .add_system(escape.after(object_selected));
+
+        let moon = harness::entity(&mut app, SpaceObject::EarthMoon);
+        app.world
+            .resource_mut::<Events<PickingEvent>>()
+            .send(PickingEvent::Selection(SelectionEvent::JustSelected(moon)));
+        harness::advance(&mut app, 1, SIMULATION_STEP);
+        assert_eq!(selected(&mut app), [SpaceObject::EarthMoon]);
+
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Escape);
+        harness::advance(&mut app, 1, SIMULATION_STEP);
+        assert!(selected(&mut app).is_empty());
+    }
+}
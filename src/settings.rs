@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A resource that's saved whenever it changes and loaded again on the next run.
+pub trait Persisted: Resource + Serialize + DeserializeOwned + Default {
+    /// The name it's stored under, unique per resource.
+    const KEY: &'static str;
+}
+
+pub trait PersistedAppExt {
+    /// Inserts `R` as loaded from storage (or its default) and keeps storage up to date.
+    fn init_persisted_resource<R: Persisted>(&mut self) -> &mut Self;
+}
+
+impl PersistedAppExt for App {
+    fn init_persisted_resource<R: Persisted>(&mut self) -> &mut Self {
+        self.insert_resource(load::<R>()).add_system(save::<R>)
+    }
+}
+
+fn load<R: Persisted>() -> R {
+    let Some(stored) = storage::read(R::KEY) else {
+        return R::default();
+    };
+
+    ron::from_str(&stored).unwrap_or_else(|err| {
+        warn!(
+            ?err,
+            key = R::KEY,
+            "Couldn't read saved settings, using the defaults"
+        );
+        R::default()
+    })
+}
+
+fn save<R: Persisted>(resource: Res<R>) {
+    if !resource.is_changed() || resource.is_added() {
+        return;
+    }
+
+    match ron::to_string(&*resource) {
+        Ok(stored) => storage::write(R::KEY, &stored),
+        Err(err) => warn!(?err, key = R::KEY, "Couldn't save settings"),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod storage {
+    use std::{fs, path::PathBuf};
+
+    fn path(key: &str) -> Option<PathBuf> {
+        Some(
+            dirs::config_dir()?
+                .join("solar-system")
+                .join(format!("{key}.ron")),
+        )
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        fs::read_to_string(path(key)?).ok()
+    }
+
+    pub fn write(key: &str, value: &str) {
+        let Some(path) = path(key) else {
+            return;
+        };
+
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, value));
+        if let Err(err) = written {
+            bevy::log::warn!(?err, ?path, "Couldn't write settings file");
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod storage {
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        local_storage()?
+            .get_item(&format!("solar-system.{key}"))
+            .ok()?
+    }
+
+    pub fn write(key: &str, value: &str) {
+        if let Some(storage) = local_storage() {
+            if storage
+                .set_item(&format!("solar-system.{key}"), value)
+                .is_err()
+            {
+                bevy::log::warn!(key, "Couldn't write settings to local storage");
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lock::RideAlong,
+    selection::{CurrentObject, Selection},
+    space::SpaceObject,
+    zoom::CameraZoom,
+    CameraOrbit, RenderStyle,
+};
+
+/// Everything needed to recreate what someone is looking at, so it can be shared as a string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneState {
+    selected: Option<SpaceObject>,
+    yaw: f32,
+    pitch: f32,
+    zoom: f32,
+    #[serde(default)]
+    ride_along: bool,
+    #[serde(default)]
+    render_style: RenderStyle,
+}
+
+impl SceneState {
+    /// A compact, URL-safe string for the state.
+    pub fn encode(&self) -> String {
+        let state = ron::to_string(self).expect("scene state is always serializable");
+        base64::encode_config(state, base64::URL_SAFE_NO_PAD)
+    }
+
+    /// Reads a string made by [`Self::encode`], ignoring surrounding whitespace.
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let state = base64::decode_config(encoded.trim(), base64::URL_SAFE_NO_PAD).ok()?;
+        ron::from_str(std::str::from_utf8(&state).ok()?).ok()
+    }
+}
+
+/// A [`SceneState`] waiting to be applied once the objects it refers to exist.
+#[derive(Resource, Default)]
+pub struct PendingSceneState(pub Option<SceneState>);
+
+impl PendingSceneState {
+    /// Reads the state from the page's URL (`#...`), so shared links open the same view.
+    /// Natively, it's read from `--state <...>` on the command line instead.
+    pub fn from_url() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let hash = web_sys::window().and_then(|window| window.location().hash().ok());
+            if let Some(hash) = hash {
+                return Self(SceneState::decode(hash.trim_start_matches('#')));
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mut args = std::env::args().skip_while(|arg| arg != "--state").skip(1);
+            if let Some(state) = args.next() {
+                return Self(SceneState::decode(&state));
+            }
+        }
+
+        Self(None)
+    }
+}
+
+// only runs once everything is spawned, so the selection has an entity to go on
+pub fn apply_scene_state(
+    mut selection: Selection,
+    mut pending: ResMut<PendingSceneState>,
+    mut orbit: ResMut<CameraOrbit>,
+    mut zoom: ResMut<CameraZoom>,
+    mut ride_along: ResMut<RideAlong>,
+    mut style: ResMut<RenderStyle>,
+) {
+    let Some(state) = pending.0.take() else {
+        return;
+    };
+
+    match state.selected {
+        Some(obj) => selection.select(obj),
+        None => selection.deselect(),
+    }
+
+    *orbit = CameraOrbit {
+        yaw: state.yaw,
+        pitch: state.pitch,
+    };
+    zoom.0 = state.zoom;
+    zoom.zoom_by(1.0); // clamps a hand-edited zoom back into range
+    ride_along.0 = state.selected.filter(|_| state.ride_along);
+    *style = state.render_style;
+}
+
+// a small window to copy the current view as a string, or paste one to jump to it
+#[allow(clippy::too_many_arguments)]
+pub fn share_ui(
+    mut egui_ctx: ResMut<EguiContext>,
+    mut pending: ResMut<PendingSceneState>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+    orbit: Res<CameraOrbit>,
+    zoom: Res<CameraZoom>,
+    ride_along: Res<RideAlong>,
+    style: Res<RenderStyle>,
+    mut pasted: Local<String>,
+) {
+    let selected = current.get_single().ok().copied();
+    let state = SceneState {
+        selected,
+        yaw: orbit.yaw,
+        pitch: orbit.pitch,
+        zoom: zoom.0,
+        ride_along: selected.is_some() && ride_along.0 == selected,
+        render_style: *style,
+    };
+
+    egui::Window::new("Share")
+        .default_open(false)
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.label("Share exactly what you're looking at.");
+
+            if ui.button("Copy share state").clicked() {
+                let encoded = state.encode();
+
+                #[cfg(target_arch = "wasm32")]
+                if let Some(window) = web_sys::window() {
+                    let _ = window.location().set_hash(&encoded);
+                }
+
+                ui.output().copied_text = encoded;
+            }
+
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut *pasted);
+                if ui.button("Paste state").clicked() {
+                    match SceneState::decode(&pasted) {
+                        Some(state) => pending.0 = Some(state),
+                        None => warn!("Couldn't read the pasted share state"),
+                    }
+                }
+            });
+        });
+}
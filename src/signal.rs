@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::space::{BodyDatabase, SpaceObject};
+
+/// How long light takes to travel one astronomical unit, in seconds.
+const LIGHT_SECONDS_PER_AU: f32 = 499.004_8;
+
+/// How many simulated seconds pass per real second while a signal travels,
+/// so a trip to Earth takes about eight seconds instead of eight minutes.
+const SIGNAL_SPEEDUP: f32 = 60.0;
+
+/// How long an arrived signal lingers before it's removed, in real seconds.
+const ARRIVED_LINGER: f32 = 3.0;
+
+/// A pulse of light sent from the Sun towards `target`.
+#[derive(Component)]
+pub struct LightSignal {
+    target: SpaceObject,
+    /// Simulated seconds since the signal was sent.
+    elapsed: f32,
+    /// Real seconds since the signal reached the target, if it has.
+    arrived: Option<f32>,
+}
+
+// move each signal along the line from the Sun to its target at the speed of light
+pub fn light_signal(
+    mut commands: Commands,
+    time: Res<Time>,
+    db: Res<BodyDatabase>,
+    mut signals: Query<(Entity, &mut LightSignal, &mut Transform)>,
+    objs: Query<(&SpaceObject, &Transform), Without<LightSignal>>,
+) {
+    for (entity, mut signal, mut transform) in signals.iter_mut() {
+        if let Some(arrived) = &mut signal.arrived {
+            *arrived += time.delta_seconds();
+            if *arrived > ARRIVED_LINGER {
+                commands.entity(entity).despawn_recursive();
+            }
+            continue;
+        }
+
+        let Some((_, target)) = objs.iter().find(|(obj, _)| **obj == signal.target) else {
+            continue;
+        };
+
+        signal.elapsed += time.delta_seconds() * SIGNAL_SPEEDUP;
+        let traveled = signal.elapsed / LIGHT_SECONDS_PER_AU;
+        let progress = traveled / signal.target.distance_from_sun(&db);
+
+        if progress >= 1.0 {
+            signal.elapsed = signal.target.distance_from_sun(&db) * LIGHT_SECONDS_PER_AU;
+            signal.arrived = Some(0.0);
+        }
+        transform.translation = target.translation * progress.min(1.0);
+    }
+}
+
+// a small window to pick a planet, send a signal to it, and time it on its way
+pub fn signal_ui(
+    mut commands: Commands,
+    mut egui_ctx: ResMut<EguiContext>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    db: Res<BodyDatabase>,
+    signals: Query<(Entity, &LightSignal)>,
+    mut target: Local<Option<SpaceObject>>,
+) {
+    let target = target.get_or_insert(SpaceObject::Earth);
+
+    egui::Window::new("Light travel")
+        .default_open(false)
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.label("Watch how long sunlight takes to reach each planet.");
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_source("signal target")
+                    .selected_text(target.name(&db))
+                    .show_ui(ui, |ui| {
+                        for obj in enum_iterator::all::<SpaceObject>()
+                            .filter(|o| o.orbits(&db) == SpaceObject::Sun && *o != SpaceObject::Sun)
+                        {
+                            ui.selectable_value(target, obj, obj.name(&db));
+                        }
+                    });
+
+                if ui.button("Send signal from Sun").clicked() {
+                    // only one signal at a time
+                    for (entity, _) in signals.iter() {
+                        commands.entity(entity).despawn_recursive();
+                    }
+
+                    commands.spawn((
+                        PbrBundle {
+                            mesh: meshes.add(Mesh::from(shape::UVSphere {
+                                radius: 300.0,
+                                sectors: 16,
+                                stacks: 16,
+                            })),
+                            material: materials.add(StandardMaterial {
+                                base_color: Color::rgb(1.0, 1.0, 0.8),
+                                unlit: true,
+                                ..default()
+                            }),
+                            ..default()
+                        },
+                        LightSignal {
+                            target: *target,
+                            elapsed: 0.0,
+                            arrived: None,
+                        },
+                    ));
+                }
+            });
+
+            for (_, signal) in signals.iter() {
+                let minutes = signal.elapsed / 60.0;
+                ui.label(if signal.arrived.is_some() {
+                    format!(
+                        "Reached {} after {minutes:.1} minutes",
+                        signal.target.name(&db)
+                    )
+                } else {
+                    format!("{minutes:.1} minutes since leaving the Sun")
+                });
+            }
+        });
+}
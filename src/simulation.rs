@@ -0,0 +1,445 @@
+use bevy::{ecs::schedule::ShouldRun, prelude::*};
+
+use crate::{
+    alignment,
+    focus::{PauseWhenUnfocused, WindowFocus},
+    selection::CurrentObject,
+    space::{BodyDatabase, ExaggerationSettings, SpaceObject},
+    AppState,
+};
+
+/// Moves everything round its orbit and about its pole, a fixed step at a time.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulationClock>()
+            .init_resource::<SimSpeed>()
+            .init_resource::<Paused>()
+            .add_system(planet_orbit.with_run_criteria(simulation_running))
+            .add_system(interpolate_orbits.after(planet_orbit))
+            .add_system(planet_spin.after(planet_orbit))
+            .add_system(
+                step_quarter_orbit
+                    .after(planet_orbit)
+                    .before(alignment::detect_alignment),
+            )
+            .add_system(
+                step_one_day
+                    .after(planet_orbit)
+                    .before(alignment::detect_alignment),
+            )
+            .add_system(apply_exaggeration.before(planet_orbit))
+            .add_system(toggle_pause.before(planet_orbit));
+    }
+}
+
+/// How many seconds of motion each fixed simulation step covers.
+pub const SIMULATION_STEP: f32 = 1.0 / 60.0;
+
+/// The most steps taken in one frame, so a long hitch doesn't snowball into an even longer one.
+const MAX_SIMULATION_STEPS: u32 = 10;
+
+/// Where an object was as of the last two fixed simulation steps.
+/// Its [`Transform`] is interpolated between them, so motion is smooth at any framerate.
+#[derive(Component)]
+pub struct OrbitState {
+    pub previous: Vec3,
+    pub current: Vec3,
+    /// How far round its orbit it is from its closest point, within one turn. This is the mean
+    /// anomaly, which grows steadily with time, so it's only the actual angle for circular orbits.
+    /// Negative velocities count down from a full turn.
+    pub angle: f32,
+}
+
+impl OrbitState {
+    pub fn at(translation: Vec3) -> Self {
+        Self {
+            previous: translation,
+            current: translation,
+            angle: 0.0,
+        }
+    }
+
+    /// How far round its orbit it is from where it started, from 0 to 1, in the direction it moves.
+    pub fn progress(&self, obj: SpaceObject, db: &BodyDatabase) -> f32 {
+        let angle = if obj.orbital_velocity(db) < 0.0 {
+            -self.angle
+        } else {
+            self.angle
+        };
+        angle.rem_euclid(std::f32::consts::TAU) / std::f32::consts::TAU
+    }
+}
+
+/// How much time has been simulated, which every position in the scene is worked out from.
+#[derive(Resource, Default)]
+pub struct SimulationClock {
+    /// Frame time that hasn't been simulated yet, always less than one step.
+    accumulator: f32,
+    /// Simulated seconds since the start. Kept as an `f64`, since angles are worked out from it
+    /// and would lose precision after a long run.
+    elapsed: f64,
+    /// How many simulated seconds the last step covered.
+    last_step: f32,
+}
+
+impl SimulationClock {
+    /// The time `interpolate_orbits` places everything at this frame, a step behind
+    /// [`Self::elapsed`].
+    pub fn shown_elapsed(&self) -> f64 {
+        let alpha = self.accumulator / SIMULATION_STEP;
+        self.elapsed - ((1.0 - alpha) * self.last_step) as f64
+    }
+
+    /// How many Earth days have gone by at [`Self::shown_elapsed`].
+    pub fn shown_days(&self, db: &BodyDatabase) -> f64 {
+        self.shown_elapsed() / SpaceObject::simulated_seconds_per_day(db) as f64
+    }
+}
+
+/// How long a year is in the simulated date, in Earth days.
+const DAYS_PER_YEAR: f64 = 365.25;
+
+/// `days` since the start of the simulation written as a date, like "Year 2, day 14".
+/// Both count from 1, so the start is "Year 1, day 1".
+pub fn simulated_date(days: f64) -> String {
+    let days = days.max(0.0);
+    let year = (days / DAYS_PER_YEAR).floor();
+    let day = (days - year * DAYS_PER_YEAR).floor();
+    format!("Year {}, day {}", year as u64 + 1, day as u64 + 1)
+}
+
+/// How many times faster than normal the simulation runs. At 0 everything stands still, though
+/// the camera can still move.
+#[derive(Resource)]
+pub struct SimSpeed(pub f32);
+
+impl SimSpeed {
+    pub const MAX: f32 = 1000.0;
+}
+
+impl Default for SimSpeed {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Whether the simulation has been paused with `Space` or the button in the main window.
+/// Everything stays where it is, and the camera and windows keep working.
+#[derive(Resource, Default)]
+pub struct Paused(pub bool);
+
+// advance the simulation in fixed steps, so where things end up doesn't depend on the framerate.
+// The steps are the same length in real time at any speed, they just cover more simulated time
+pub fn planet_orbit(
+    time: Res<Time>,
+    speed: Res<SimSpeed>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    mut clock: ResMut<SimulationClock>,
+    mut planet_q: Query<(&mut OrbitState, &SpaceObject)>,
+) {
+    clock.accumulator = (clock.accumulator + time.delta_seconds())
+        .min(SIMULATION_STEP * MAX_SIMULATION_STEPS as f32);
+
+    while clock.accumulator >= SIMULATION_STEP {
+        clock.accumulator -= SIMULATION_STEP;
+
+        for (mut state, _) in planet_q.iter_mut() {
+            state.previous = state.current;
+        }
+        step_orbits(
+            &mut planet_q,
+            &db,
+            &exaggeration,
+            &mut clock,
+            SIMULATION_STEP * speed.0,
+        );
+    }
+}
+
+// turn everything about its tilted pole, keeping day lengths in step with the orbits
+fn planet_spin(
+    clock: Res<SimulationClock>,
+    db: Res<BodyDatabase>,
+    mut objs: Query<(&SpaceObject, &mut Transform)>,
+) {
+    let elapsed = clock.shown_elapsed();
+
+    for (obj, mut transform) in objs.iter_mut() {
+        transform.rotation =
+            obj.pole_orientation(&db) * Quat::from_rotation_z(obj.spin_angle(elapsed, &db));
+    }
+}
+
+/// How far round its orbit `obj` is after `elapsed` simulated seconds, as its mean anomaly.
+fn orbit_angle(obj: SpaceObject, elapsed: f64, db: &BodyDatabase) -> f32 {
+    (obj.orbital_velocity(db) as f64 * elapsed).rem_euclid(std::f64::consts::TAU) as f32
+}
+
+// put everything where it is after `elapsed` simulated seconds, planets first so the moons can
+// be placed around them
+fn place_orbits(
+    planet_q: &mut Query<(&mut OrbitState, &SpaceObject)>,
+    db: &BodyDatabase,
+    exaggeration: &ExaggerationSettings,
+    elapsed: f64,
+) {
+    let mut main_planets = Vec::with_capacity(8);
+
+    for (mut state, &planet) in planet_q
+        .iter_mut()
+        .filter(|(_, p)| p.orbits(db) == SpaceObject::Sun)
+    {
+        state.angle = orbit_angle(planet, elapsed, db);
+        state.current = planet.orbit_position(state.angle, db, exaggeration);
+        main_planets.push((state.current, planet));
+    }
+    for (mut state, &planet) in planet_q
+        .iter_mut()
+        .filter(|(_, o)| o.orbits(db) != SpaceObject::Sun)
+    {
+        let Some(parent) = main_planets
+            .iter()
+            .find_map(|(position, orbit)| (planet.orbits(db) == *orbit).then_some(*position))
+        else {
+            continue;
+        };
+
+        state.angle = orbit_angle(planet, elapsed, db);
+        state.current = parent + planet.orbit_position(state.angle, db, exaggeration);
+
+        // moons have to circle their planet, between its closest and furthest points
+        debug_assert!(
+            {
+                let semi_major_axis = planet.scaled_distance(db, exaggeration);
+                let pushed_out = planet.display_distance(db, exaggeration) - semi_major_axis;
+                let slack = semi_major_axis * 1e-3 + 1e-3;
+                let distance = state.current.distance(parent) - pushed_out;
+                (semi_major_axis * (1.0 - planet.eccentricity(db)) - slack
+                    ..=semi_major_axis * (1.0 + planet.eccentricity(db)) + slack)
+                    .contains(&distance)
+            },
+            "{} wandered away from {}",
+            planet.name(db),
+            planet.orbits(db).name(db)
+        );
+    }
+}
+
+/// Moves the simulation on by `delta` seconds.
+pub fn step_orbits(
+    planet_q: &mut Query<(&mut OrbitState, &SpaceObject)>,
+    db: &BodyDatabase,
+    exaggeration: &ExaggerationSettings,
+    clock: &mut SimulationClock,
+    delta: f32,
+) {
+    clock.elapsed += delta as f64;
+    clock.last_step = delta;
+    place_orbits(planet_q, db, exaggeration, clock.elapsed);
+}
+
+/// Puts everything where it is at `elapsed` simulated seconds, without interpolating there.
+fn jump_orbits(
+    planet_q: &mut Query<(&mut OrbitState, &SpaceObject)>,
+    db: &BodyDatabase,
+    exaggeration: &ExaggerationSettings,
+    clock: &mut SimulationClock,
+    elapsed: f64,
+) {
+    clock.elapsed = elapsed;
+    place_orbits(planet_q, db, exaggeration, elapsed);
+    for (mut state, _) in planet_q.iter_mut() {
+        state.previous = state.current;
+    }
+}
+
+// `.` moves the simulation on until the selected object is a quarter of the way further round its
+// orbit, for walking through its motion one step at a time
+fn step_quarter_orbit(
+    kbd: Res<Input<KeyCode>>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    mut clock: ResMut<SimulationClock>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+    mut planet_q: Query<(&mut OrbitState, &SpaceObject)>,
+) {
+    if !kbd.just_pressed(KeyCode::Period) {
+        return;
+    }
+    let Ok(&obj) = current.get_single() else {
+        return;
+    };
+    let speed = obj.orbital_velocity(&db).abs() as f64;
+    if speed <= 0.0 {
+        return;
+    }
+
+    // a little slack, so landing just short of a quarter doesn't make the next press a no-op
+    let quarter = std::f64::consts::FRAC_PI_2;
+    let quarters = (speed * clock.elapsed / quarter + 1e-6).floor() + 1.0;
+    let elapsed = quarters * quarter / speed;
+    info!(
+        "Stepping {} a quarter orbit ({:.0} seconds)",
+        obj.name(&db),
+        elapsed - clock.elapsed
+    );
+    jump_orbits(&mut planet_q, &db, &exaggeration, &mut clock, elapsed);
+}
+
+// `,` moves the simulation on by one Earth day, for walking through motions a day at a time
+fn step_one_day(
+    kbd: Res<Input<KeyCode>>,
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    mut clock: ResMut<SimulationClock>,
+    mut planet_q: Query<(&mut OrbitState, &SpaceObject)>,
+) {
+    if !kbd.just_pressed(KeyCode::Comma) {
+        return;
+    }
+
+    let elapsed = clock.elapsed + SpaceObject::simulated_seconds_per_day(&db) as f64;
+    info!("Stepping one day");
+    jump_orbits(&mut planet_q, &db, &exaggeration, &mut clock, elapsed);
+}
+
+// place everything between its last two simulated positions
+pub fn interpolate_orbits(
+    clock: Res<SimulationClock>,
+    mut objs: Query<(&OrbitState, &mut Transform)>,
+) {
+    let alpha = clock.accumulator / SIMULATION_STEP;
+
+    for (state, mut transform) in objs.iter_mut() {
+        transform.translation = state.previous.lerp(state.current, alpha);
+    }
+}
+
+// push everything out to (or pull it in from) its new distance, keeping it where it is in its orbit
+pub fn apply_exaggeration(
+    db: Res<BodyDatabase>,
+    exaggeration: Res<ExaggerationSettings>,
+    mut clock: ResMut<SimulationClock>,
+    mut objs: Query<(&mut OrbitState, &SpaceObject)>,
+) {
+    if !exaggeration.is_changed() || exaggeration.is_added() {
+        return;
+    }
+    let elapsed = clock.elapsed;
+    jump_orbits(&mut objs, &db, &exaggeration, &mut clock, elapsed);
+}
+
+// run criteria for the systems that move things around
+pub fn simulation_running(
+    state: Res<State<AppState>>,
+    focus: Res<WindowFocus>,
+    pause_when_unfocused: Res<PauseWhenUnfocused>,
+    paused: Res<Paused>,
+) -> ShouldRun {
+    if *state.current() == AppState::Loading || paused.0 || (pause_when_unfocused.0 && !focus.0) {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+// `Space` stops and starts the simulation, without touching where anything is
+fn toggle_pause(kbd: Res<Input<KeyCode>>, mut paused: ResMut<Paused>) {
+    if kbd.just_pressed(KeyCode::Space) {
+        paused.0 = !paused.0;
+        info!(
+            "{} the simulation",
+            if paused.0 { "Paused" } else { "Resumed" }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness;
+
+    // where everything is after `steps` simulation steps, run as `frames` equal frames
+    fn simulate(steps: f32, frames: u32) -> (f64, Vec<(SpaceObject, Vec3)>) {
+        let mut app = harness::app();
+        harness::advance(&mut app, frames, steps * SIMULATION_STEP / frames as f32);
+
+        let mut positions: Vec<_> = app
+            .world
+            .query::<(&SpaceObject, &OrbitState)>()
+            .iter(&app.world)
+            .map(|(obj, state)| (*obj, state.current))
+            .collect();
+        positions.sort_by_key(|(obj, _)| *obj as usize);
+        (app.world.resource::<SimulationClock>().elapsed, positions)
+    }
+
+    #[test]
+    fn dates_count_from_one() {
+        assert_eq!(simulated_date(0.0), "Year 1, day 1");
+        assert_eq!(simulated_date(13.9), "Year 1, day 14");
+        assert_eq!(simulated_date(365.0), "Year 1, day 366");
+        assert_eq!(simulated_date(365.25 + 13.5), "Year 2, day 14");
+    }
+
+    #[test]
+    fn one_day_step_moves_the_clock_on_a_day() {
+        let mut app = harness::app();
+        let db = BodyDatabase::embedded();
+
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Comma);
+        app.world.resource_mut::<SimSpeed>().0 = 0.0;
+        harness::advance(&mut app, 1, SIMULATION_STEP);
+
+        let days = app.world.resource::<SimulationClock>().elapsed
+            / SpaceObject::simulated_seconds_per_day(&db) as f64;
+        assert!((days - 1.0).abs() < 1e-6, "{days}");
+    }
+
+    #[test]
+    fn same_state_at_any_frame_rate() {
+        // half a step over, so rounding in the frame times can't tip it into another step
+        let steps = 120.5;
+        let expected = simulate(steps, 241);
+        assert!(expected.0 > 0.0);
+        for frames in [61, 13] {
+            assert_eq!(simulate(steps, frames), expected, "in {frames} frames");
+        }
+    }
+
+    #[test]
+    fn selected_moon_keeps_its_distance() {
+        let mut app = harness::app();
+        let db = BodyDatabase::embedded();
+        let exaggeration = ExaggerationSettings::default();
+        let (moon, planet) = (SpaceObject::EarthMoon, SpaceObject::Earth);
+        let moon_entity = harness::entity(&mut app, moon);
+        let planet_entity = harness::entity(&mut app, planet);
+        app.world.entity_mut(moon_entity).insert(CurrentObject);
+
+        // between its closest and furthest points, pushed out from the planet's surface
+        let semi_major_axis = moon.scaled_distance(&db, &exaggeration);
+        let pushed_out = moon.display_distance(&db, &exaggeration) - semi_major_axis;
+        let slack = semi_major_axis * 1e-3;
+        let closest = pushed_out + semi_major_axis * (1.0 - moon.eccentricity(&db)) - slack;
+        let furthest = pushed_out + semi_major_axis * (1.0 + moon.eccentricity(&db)) + slack;
+
+        let start = app.world.get::<OrbitState>(moon_entity).unwrap().angle;
+        for _ in 0..100 {
+            harness::advance(&mut app, 10, SIMULATION_STEP);
+            let moon_position = app.world.get::<OrbitState>(moon_entity).unwrap().current;
+            let planet_position = app.world.get::<OrbitState>(planet_entity).unwrap().current;
+            let distance = moon_position.distance(planet_position);
+            assert!((closest..=furthest).contains(&distance), "{distance}");
+        }
+        assert_ne!(
+            app.world.get::<OrbitState>(moon_entity).unwrap().angle,
+            start
+        );
+    }
+}
@@ -1,10 +1,301 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use serde::Deserialize;
 
 const ASTRO_UNIT: f32 = 149_597_870.7;
 
 const GRAV: f32 = 6.674_08e-11;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component, enum_iterator::Sequence)]
+/// How many scene units one astronomical unit is, before [`ExaggerationSettings::distance`].
+const SCENE_UNITS_PER_AU: f32 = 69_570.0;
+
+/// How many scene units one kilometer is, before any exaggeration.
+const SCENE_UNITS_PER_KM: f32 = SCENE_UNITS_PER_AU / ASTRO_UNIT;
+
+/// How many times less the Sun is blown up than everything else, so it doesn't swallow Mercury.
+const SUN_SIZE_DAMPING: f32 = 14.4;
+
+/// How many Newton's method steps are taken at most when solving Kepler's equation.
+const KEPLER_ITERATIONS: usize = 10;
+
+/// How many of its own radii of empty space a moon keeps from its planet's surface, and from the
+/// next moon in.
+const MOON_MARGIN: f32 = 2.0;
+
+/// How much bigger sizes and distances are drawn than they really are, relative to each other.
+/// At 1 for both, everything is to scale, which mostly means everything is invisible.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct ExaggerationSettings {
+    /// How many times bigger objects are drawn. The Sun is exaggerated a lot less.
+    pub size: f32,
+    /// How many times further apart objects are drawn.
+    pub distance: f32,
+}
+
+impl ExaggerationSettings {
+    /// Big enough to see everything, while keeping the planets well apart.
+    pub const DIAGRAM: Self = Self {
+        size: 309.0,
+        distance: 1.0,
+    };
+    pub const REALISTIC: Self = Self {
+        size: 1.0,
+        distance: 1.0,
+    };
+}
+
+impl Default for ExaggerationSettings {
+    fn default() -> Self {
+        Self::DIAGRAM
+    }
+}
+
+/// The numbers behind a [`SpaceObject`], stored in `assets/bodies.ron`.
+/// See the methods of the same names on [`SpaceObject`] for what each one means.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BodyData {
+    pub name: String,
+    pub radius: f32,
+    pub distance_from_parent: f32,
+    pub mass: f32,
+    pub inclination: f32,
+    pub eccentricity: f32,
+    pub orbits: SpaceObject,
+    pub temperature: f32,
+    pub period_of_revolution: f32,
+    pub period_of_rotation: f32,
+    pub axial_tilt: f32,
+    pub albedo: f32,
+    pub number_of_known_moons: usize,
+    pub magnetic_field_strength: Option<f32>,
+    pub number_of_rings: usize,
+    pub rings: Option<(f32, f32)>,
+    pub fun_fact: Option<String>,
+}
+
+/// The [`BodyData`] for every [`SpaceObject`], which everything reads the numbers from.
+#[derive(Resource, Debug, Clone, Deserialize)]
+pub struct BodyDatabase(HashMap<SpaceObject, BodyData>);
+
+/// Where the data lives, relative to the crate, for reloading it while developing.
+pub const BODY_DATABASE_PATH: &str = "assets/bodies.ron";
+
+impl BodyDatabase {
+    /// The copy compiled into the binary, so it's available right away (even before the asset
+    /// server has loaded anything).
+    pub fn embedded() -> Self {
+        Self::parse(include_str!("../assets/bodies.ron")).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Parses a database, making sure every object has a sensible entry.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let database: Self =
+            ron::from_str(source).map_err(|err| format!("invalid body data: {err}"))?;
+        database.validate()?;
+        Ok(database)
+    }
+
+    // catch data entry mistakes, which would otherwise show up as objects missing from the
+    // scene or the orbit system looping forever
+    fn validate(&self) -> Result<(), String> {
+        let all = || enum_iterator::all::<SpaceObject>();
+
+        if let Some(missing) = all().find(|obj| !self.0.contains_key(obj)) {
+            return Err(format!("no body data for {missing:?}"));
+        }
+        if self.0[&SpaceObject::Sun].orbits != SpaceObject::Sun {
+            return Err("the Sun has to orbit itself".to_string());
+        }
+
+        for obj in all() {
+            let data = &self.0[&obj];
+            if data.name.trim().is_empty() {
+                return Err(format!("{obj:?} needs a name"));
+            }
+            if !(data.radius.is_finite() && data.radius > 0.0) {
+                return Err(format!("{obj:?} needs a positive radius"));
+            }
+            if !(data.mass.is_finite() && data.mass > 0.0) {
+                return Err(format!("{obj:?} needs a positive mass"));
+            }
+            if !(data.distance_from_parent.is_finite() && data.distance_from_parent >= 0.0) {
+                return Err(format!("{obj:?} can't have a negative distance"));
+            }
+            if !(0.0..1.0).contains(&data.eccentricity) {
+                return Err(format!(
+                    "{obj:?}'s eccentricity should be from 0 up to 1, for a closed orbit"
+                ));
+            }
+            if obj != SpaceObject::Sun
+                && !(data.period_of_revolution.is_finite() && data.period_of_revolution > 0.0)
+            {
+                return Err(format!("{obj:?} needs a positive period of revolution"));
+            }
+            if !(data.albedo.is_finite() && data.albedo >= 0.0) {
+                return Err(format!("{obj:?} can't have a negative albedo"));
+            }
+            if !(0.0..=180.0).contains(&data.axial_tilt) {
+                return Err(format!(
+                    "{obj:?}'s axial tilt should be between 0° and 180°"
+                ));
+            }
+
+            // every chain of orbits has to end at the Sun, without going around in circles
+            let mut parent = obj;
+            for _ in 0..self.0.len() {
+                if parent == SpaceObject::Sun {
+                    break;
+                }
+                parent = self.0[&parent].orbits;
+            }
+            if parent != SpaceObject::Sun {
+                return Err(format!("{obj:?}'s orbits never reach the Sun"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reloads the [`BodyDatabase`] whenever `assets/bodies.ron` changes on disk, so numbers can be
+/// tweaked without recompiling. Only sizes and periods that are read every frame update live;
+/// anything baked in at spawn (like meshes) needs a restart.
+#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+pub fn reload_body_data(
+    mut db: ResMut<BodyDatabase>,
+    mut last_modified: Local<Option<std::time::SystemTime>>,
+) {
+    let Ok(modified) = std::fs::metadata(BODY_DATABASE_PATH).and_then(|meta| meta.modified()) else {
+        return;
+    };
+    if last_modified
+        .replace(modified)
+        .map_or(true, |last| last == modified)
+    {
+        return;
+    }
+
+    match std::fs::read_to_string(BODY_DATABASE_PATH)
+        .map_err(|err| err.to_string())
+        .and_then(|source| BodyDatabase::parse(&source))
+    {
+        Ok(database) => {
+            *db = database;
+            info!("reloaded {BODY_DATABASE_PATH}");
+        }
+        Err(err) => warn!("couldn't reload {BODY_DATABASE_PATH}: {err}"),
+    }
+}
+
+/// The ellipse an object follows in the scene, as returned by [`SpaceObject::orbital_elements`].
+/// What it orbits sits at one focus, and it's closest on the X axis of its plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitalElements {
+    /// Half the longest width of the orbit, in scene units.
+    pub semi_major_axis: f32,
+    pub eccentricity: f32,
+    /// The rotation from the XZ plane to the plane of the orbit.
+    pub plane: Quat,
+}
+
+impl OrbitalElements {
+    /// Half the shortest width of the orbit, in scene units.
+    pub fn semi_minor_axis(&self) -> f32 {
+        self.semi_major_axis * (1.0 - self.eccentricity * self.eccentricity).sqrt()
+    }
+
+    /// Where the middle of the ellipse is, from the focus.
+    pub fn center(&self) -> Vec3 {
+        self.plane * Vec3::new(-self.semi_major_axis * self.eccentricity, 0.0, 0.0)
+    }
+}
+
+/// Solves Kepler's equation, M = E - e sin E, for the eccentric anomaly E.
+pub fn eccentric_anomaly(mean_anomaly: f32, eccentricity: f32) -> f32 {
+    // starting from the mean anomaly can overshoot on very stretched orbits
+    let mut anomaly = if eccentricity > 0.8 {
+        std::f32::consts::PI
+    } else {
+        mean_anomaly
+    };
+    for _ in 0..KEPLER_ITERATIONS {
+        let step = (anomaly - eccentricity * anomaly.sin() - mean_anomaly)
+            / (1.0 - eccentricity * anomaly.cos());
+        anomaly -= step;
+        if step.abs() < 1e-6 {
+            break;
+        }
+    }
+    anomaly
+}
+
+/// The closest two objects get to each other, as found by [`SpaceObject::closest_approach`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Approach {
+    /// The separation at the closest point in astronomical units.
+    pub distance: f32,
+    /// How many Earth days from now the closest point happens.
+    pub days: f32,
+}
+
+/// What kind of object something is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Star,
+    Terrestrial,
+    GasGiant,
+    IceGiant,
+    DwarfPlanet,
+    Moon,
+}
+
+impl Category {
+    /// A readable name for the category, like "Gas giant".
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Star => "Star",
+            Self::Terrestrial => "Terrestrial planet",
+            Self::GasGiant => "Gas giant",
+            Self::IceGiant => "Ice giant",
+            Self::DwarfPlanet => "Dwarf planet",
+            Self::Moon => "Moon",
+        }
+    }
+
+    /// A flat color for drawing objects of this category, like in a diagram.
+    pub fn color(self) -> Color {
+        match self {
+            Self::Star => Color::rgb(1.0, 0.8, 0.2),
+            Self::Terrestrial => Color::rgb(0.8, 0.45, 0.3),
+            Self::GasGiant => Color::rgb(0.85, 0.7, 0.5),
+            Self::IceGiant => Color::rgb(0.4, 0.7, 0.9),
+            Self::DwarfPlanet => Color::rgb(0.75, 0.6, 0.8),
+            Self::Moon => Color::rgb(0.6, 0.6, 0.6),
+        }
+    }
+}
+
+/// A near whole-number ratio between two objects' orbital periods,
+/// as found by [`SpaceObject::resonances`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resonance {
+    /// The other object in the resonance.
+    pub with: SpaceObject,
+    /// The ratio of the longer period to the shorter one, like `(3, 2)` for Pluto and Neptune.
+    pub ratio: (u32, u32),
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Component,
+    enum_iterator::Sequence,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 /// Space objects, including moons (a max of 5 per planet), planets, and the Sun.
 pub enum SpaceObject {
     Sun,
@@ -56,447 +347,781 @@ pub enum SpaceObject {
 }
 
 impl SpaceObject {
+    /// This object's entry in the [`BodyDatabase`].
+    fn data(self, db: &BodyDatabase) -> &BodyData {
+        &db.0[&self]
+    }
+
+    /// Finds an object by its name, ignoring case and a leading "The", so `mars` and `sun` work.
+    pub fn from_name(name: &str, db: &BodyDatabase) -> Option<Self> {
+        let name = name.trim();
+        enum_iterator::all::<Self>().find(|obj| {
+            let full = obj.name(db);
+            full.eq_ignore_ascii_case(name)
+                || full
+                    .strip_prefix("The ")
+                    .map_or(false, |short| short.eq_ignore_ascii_case(name))
+                || format!("{obj:?}").eq_ignore_ascii_case(name)
+        })
+    }
+
     /// The name of the object.
-    pub fn name(self) -> &'static str {
+    pub fn name(self, db: &BodyDatabase) -> &str {
+        &self.data(db).name
+    }
+
+    /// What kind of object this is.
+    pub fn category(self) -> Category {
         match self {
-            Self::Sun => "The Sun",
+            Self::Sun => Category::Star,
+            Self::Mercury | Self::Venus | Self::Earth | Self::Mars => Category::Terrestrial,
+            Self::Jupiter | Self::Saturn => Category::GasGiant,
+            Self::Uranus | Self::Neptune => Category::IceGiant,
+            Self::Pluto => Category::DwarfPlanet,
+            _ => Category::Moon,
+        }
+    }
 
-            Self::Mercury => "Mercury",
+    /// The radius of the planet in kilometers.
+    pub fn radius(self, db: &BodyDatabase) -> f32 {
+        self.data(db).radius
+    }
 
-            Self::Venus => "Venus",
+    /// The average distance from the object it orbits ([`Self::orbits`]) in astronomical units.
+    /// For planets that's the Sun, but for moons it's their planet.
+    pub fn distance_from_parent(self, db: &BodyDatabase) -> f32 {
+        self.data(db).distance_from_parent
+    }
 
-            Self::Earth => "Earth",
-            Self::EarthMoon => "The Moon",
+    /// [`Self::distance_from_parent`] in kilometers, for showing moons' distances, which are tiny
+    /// fractions of an AU.
+    pub fn distance_from_parent_km(self, db: &BodyDatabase) -> f32 {
+        self.distance_from_parent(db) * ASTRO_UNIT
+    }
+
+    /// The average distance from the Sun in astronomical units.
+    /// Moons are as far out as the planet they orbit (plus their own small distance from it).
+    pub fn distance_from_sun(self, db: &BodyDatabase) -> f32 {
+        if self == Self::Sun {
+            0.0
+        } else {
+            self.distance_from_parent(db) + self.orbits(db).distance_from_sun(db)
+        }
+    }
 
-            Self::Mars => "Mars",
-            Self::Phobos => "Phobos",
-            Self::Deimos => "Deimos",
+    /// The mass of the planet in kilograms.
+    /// The Sun has a mass of 1.9891e30 kg.
+    pub fn mass(self, db: &BodyDatabase) -> f32 {
+        self.data(db).mass
+    }
 
-            Self::Jupiter => "Jupiter",
-            Self::Metis => "Metis",
-            Self::Adrastea => "Adrastea",
-            Self::Amalthea => "Amalthea",
-            Self::Thebe => "Thebe",
-            Self::Io => "Io",
+    /// The pull of gravity at the surface (or cloud tops) in meters per second squared.
+    pub fn surface_gravity(self, db: &BodyDatabase) -> f32 {
+        let radius = self.radius(db) * 1_000.0;
+        GRAV * self.mass(db) / (radius * radius)
+    }
 
-            Self::Saturn => "Saturn",
-            Self::Enceladus => "Enceladus",
-            Self::Mimas => "Mimas",
-            Self::Tethys => "Tethys",
-            Self::Dione => "Dione",
-            Self::Rhea => "Rhea",
-            Self::Titan => "Titan",
+    /// The average density in kilograms per cubic meter, taking the object to be a sphere.
+    pub fn density(self, db: &BodyDatabase) -> f32 {
+        let radius = self.radius(db) * 1_000.0;
+        self.mass(db) / (4.0 / 3.0 * std::f32::consts::PI * radius * radius * radius)
+    }
 
-            Self::Uranus => "Uranus",
-            Self::Miranda => "Miranda",
-            Self::Ariel => "Ariel",
-            Self::Umbriel => "Umbriel",
-            Self::Titania => "Titania",
-            Self::Oberon => "Oberon",
+    /// How fast something has to leave the surface to never fall back, in meters per second.
+    pub fn escape_velocity(self, db: &BodyDatabase) -> f32 {
+        (2.0 * GRAV * self.mass(db) / (self.radius(db) * 1_000.0)).sqrt()
+    }
 
-            Self::Neptune => "Neptune",
-            Self::Triton => "Triton",
-            Self::Nereid => "Nereid",
-            Self::Proteus => "Proteus",
-            Self::Larissa => "Larissa",
-            Self::Halimede => "Halimede",
+    /// The average orbital velocity in meters per second around [`Self::orbits`].
+    /// Negative for [retrograde](Self::orbits_retrograde) orbits.
+    pub fn orbital_velocity(self, db: &BodyDatabase) -> f32 {
+        if self == Self::Sun {
+            return 0.0;
+        }
 
-            Self::Pluto => "Pluto",
-            Self::Charon => "Charon",
-            Self::Nix => "Nix",
-            Self::Hydra => "Hydra",
-            Self::Kerberos => "Kerberos",
-            Self::Styx => "Styx",
+        let distance_from = self.distance_from_parent(db) * self.orbits(db).radius(db) * ASTRO_UNIT
+            + self.radius(db);
+        let speed = (GRAV * self.orbits(db).mass(db) / distance_from).sqrt() / 10_000.0;
+        if self.orbits_retrograde(db) {
+            -speed
+        } else {
+            speed
         }
     }
 
-    /// The radius of the planet in kilometers.
-    pub fn radius(self) -> f32 {
-        match self {
-            Self::Sun => 695_700.0,
-
-            Self::Mercury => 2_439.7,
-
-            Self::Venus => 6_051.8,
-
-            Self::Earth => 6_371.0,
-            Self::EarthMoon => 1_737.4,
-
-            Self::Mars => 3_389.5,
-            Self::Phobos => 11.1,
-            Self::Deimos => 6.2,
-
-            Self::Jupiter => 69_911.0,
-            Self::Metis => 21.5,
-            Self::Adrastea => 8.2,
-            Self::Amalthea => 83.5,
-            Self::Thebe => 49.3,
-            Self::Io => 1_821.6,
-
-            Self::Saturn => 58_232.0,
-            Self::Enceladus => 252.1,
-            Self::Mimas => 198.2,
-            Self::Tethys => 533.0,
-            Self::Dione => 561.4,
-            Self::Rhea => 764.3,
-            Self::Titan => 2_575.5,
-
-            Self::Uranus => 25_362.0,
-            Self::Miranda => 240.8,
-            Self::Ariel => 578.9,
-            Self::Umbriel => 584.7,
-            Self::Titania => 788.9,
-            Self::Oberon => 761.4,
-
-            Self::Neptune => 24_622.0,
-            Self::Triton => 1_353.4,
-            Self::Nereid => 170.0,
-            Self::Proteus => 210.0,
-            Self::Larissa => 97.0,
-            Self::Halimede => 31.0,
-
-            Self::Pluto => 1_188.3,
-            Self::Charon => 606.0,
-            Self::Nix => 23.0,
-            Self::Hydra => 30.0,
-            Self::Kerberos => 12.0,
-            Self::Styx => 10.0,
-        }
-    }
-
-    /// The average distance from the planet it orbits in astronomical units.
-    pub fn distance(self) -> f32 {
-        match self {
-            Self::Sun => 0.0,
+    /// How fast it really moves around [`Self::orbits`], in kilometers per second, assuming a
+    /// circular orbit. Unlike [`Self::orbital_velocity`] this isn't tuned for the scene.
+    pub fn orbital_speed(self, db: &BodyDatabase) -> f32 {
+        if self == Self::Sun {
+            return 0.0;
+        }
 
-            Self::Mercury => 0.387,
+        let distance = self.distance_from_parent(db) * ASTRO_UNIT * 1_000.0;
+        (GRAV * self.orbits(db).mass(db) / distance).sqrt() / 1_000.0
+    }
 
-            Self::Venus => 0.723,
+    /// The inclination of the orbit in degrees.
+    /// Planets are measured against the ecliptic and moons against their planet's equator.
+    /// Anything over 90° orbits backwards (retrograde), like Triton.
+    pub fn inclination(self, db: &BodyDatabase) -> f32 {
+        self.data(db).inclination
+    }
 
-            Self::Earth => 1.0,
-            Self::EarthMoon => 0.00257,
+    /// How stretched the orbit is, from 0 for a circle up to (but not including) 1.
+    /// The Sun doesn't orbit anything, so it's 0.
+    pub fn eccentricity(self, db: &BodyDatabase) -> f32 {
+        self.data(db).eccentricity
+    }
 
-            Self::Mars => 1.524,
-            Self::Phobos => 0.000039,
-            Self::Deimos => 0.000157,
+    /// Whether this object orbits in the opposite direction to its parent's spin.
+    pub fn orbits_retrograde(self, db: &BodyDatabase) -> bool {
+        self.inclination(db) > 90.0
+    }
 
-            Self::Jupiter => 5.203,
-            Self::Metis => 0.00128,
-            Self::Adrastea => 0.0015,
-            Self::Amalthea => 0.0032,
-            Self::Thebe => 0.00422,
-            Self::Io => 0.00282,
+    /// The rotation from the ecliptic to the plane this object orbits in.
+    /// Planets are tilted from the ecliptic, and moons from their planet's equator, which stands
+    /// up along [`Self::rotation_axis`] however far the planet leans.
+    /// The line of nodes is the X axis, which is where every object starts out.
+    /// Retrograde orbits are tilted the short way, since [`Self::orbital_velocity`] is
+    /// already negative for them.
+    pub fn orbital_plane(self, db: &BodyDatabase) -> Quat {
+        let inclination = if self.orbits_retrograde(db) {
+            self.inclination(db) - 180.0
+        } else {
+            self.inclination(db)
+        };
+        self.reference_plane(db) * Quat::from_rotation_x(inclination.to_radians())
+    }
 
-            Self::Saturn => 9.537,
-            Self::Enceladus => 0.00317,
-            Self::Mimas => 0.0196,
-            Self::Tethys => 0.0384,
-            Self::Dione => 0.0563,
-            Self::Rhea => 0.126,
-            Self::Titan => 0.0847,
+    /// The rotation from the ecliptic to the plane this object's orbit and pole are tilted from:
+    /// the ecliptic itself for planets, and their planet's equator for moons.
+    fn reference_plane(self, db: &BodyDatabase) -> Quat {
+        let parent = self.orbits(db);
+        if parent == Self::Sun {
+            return Quat::IDENTITY;
+        }
+        // the pole orientation stands Z up along the pole, so Y is turned onto Z first
+        parent.pole_orientation(db) * Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)
+    }
 
-            Self::Uranus => 19.191,
-            Self::Miranda => 0.00129,
-            Self::Ariel => 0.00195,
-            Self::Umbriel => 0.00266,
-            Self::Titania => 0.00817,
-            Self::Oberon => 0.0127,
+    /// The object that this object orbits.
+    /// The Sun orbits itself.
+    pub fn orbits(self, db: &BodyDatabase) -> Self {
+        self.data(db).orbits
+    }
 
-            Self::Neptune => 30.069,
-            Self::Triton => 0.00237,
-            Self::Nereid => 0.036,
-            Self::Proteus => 0.0077,
-            Self::Larissa => 0.00073,
-            Self::Halimede => 0.0379,
+    /// A fun fact about the object, or an apology for objects that don't have one yet.
+    pub fn fun_fact(self, db: &BodyDatabase) -> &str {
+        self.data(db)
+            .fun_fact
+            .as_deref()
+            .unwrap_or("Sorry, no fun fact available for this planet yet!")
+    }
 
-            Self::Pluto => 39.482,
-            Self::Charon => 0.00157,
-            Self::Nix => 0.002,
-            Self::Hydra => 0.0045,
-            Self::Kerberos => 0.00347,
-            Self::Styx => 0.0078,
-        }
+    /// The radius in the scene, exaggerated by [`ExaggerationSettings::size`].
+    pub fn scaled_radius(self, db: &BodyDatabase, exaggeration: &ExaggerationSettings) -> f32 {
+        let size = if self == Self::Sun {
+            (exaggeration.size / SUN_SIZE_DAMPING).max(1.0)
+        } else {
+            exaggeration.size
+        };
+        self.radius(db) * SCENE_UNITS_PER_KM * size
     }
 
-    /// The mass of the planet in kilograms.
-    /// The Sun has a mass of 1.9891e30 kg.
-    pub fn mass(self) -> f32 {
-        match self {
-            Self::Sun => 1.9891e30,
+    /// The distance from what it orbits in the scene, exaggerated by
+    /// [`ExaggerationSettings::distance`].
+    pub fn scaled_distance(self, db: &BodyDatabase, exaggeration: &ExaggerationSettings) -> f32 {
+        self.distance_from_parent(db) * SCENE_UNITS_PER_AU * exaggeration.distance
+    }
 
-            Self::Mercury => 3.3011e23,
+    /// How far from what it orbits the object is actually drawn.
+    /// Moons are measured from their planet's surface instead of its center, since the planets are
+    /// drawn much bigger than they really are and would otherwise swallow their moons. For the
+    /// same reason, each moon is pushed out far enough to clear the one inside it.
+    /// Everything shown to the user still uses the real [`Self::distance_from_parent`].
+    pub fn display_distance(self, db: &BodyDatabase, exaggeration: &ExaggerationSettings) -> f32 {
+        let parent = self.orbits(db);
+        if parent == Self::Sun {
+            return self.scaled_distance(db, exaggeration);
+        }
 
-            Self::Venus => 4.8675e24,
+        // lay out the planet's moons from the inside out, up to this one
+        let mut moons: Vec<Self> = parent.moons(db).collect();
+        moons.sort_by(|a, b| {
+            a.distance_from_parent(db)
+                .total_cmp(&b.distance_from_parent(db))
+        });
+
+        // the outer edge of the last moon placed, or the planet's surface
+        let mut inner_edge = parent.scaled_radius(db, exaggeration);
+        let mut distance = 0.0;
+        for moon in moons {
+            let margin = moon.scaled_radius(db, exaggeration) * (1.0 + MOON_MARGIN);
+            distance = (parent.scaled_radius(db, exaggeration)
+                + margin
+                + moon.scaled_distance(db, exaggeration))
+            .max(inner_edge + margin);
+            if moon == self {
+                break;
+            }
+            inner_edge = distance + moon.scaled_radius(db, exaggeration);
+        }
+        distance
+    }
 
-            Self::Earth => 5.97237e24,
-            Self::EarthMoon => 7.342e22,
+    /// Neighbouring objects whose paths overlap as drawn, going by their average distances, so
+    /// they'd pass through each other.
+    /// Moons are laid out to avoid this, so it should only turn up for planets at extreme
+    /// exaggerations.
+    pub fn overlapping_orbits(
+        db: &BodyDatabase,
+        exaggeration: &ExaggerationSettings,
+    ) -> Vec<(Self, Self)> {
+        let mut overlapping = Vec::new();
+        let distance = |obj: Self| obj.display_distance(db, exaggeration);
+        let radius = |obj: Self| obj.scaled_radius(db, exaggeration);
+
+        for parent in enum_iterator::all::<Self>() {
+            let mut children: Vec<Self> = parent.moons(db).collect();
+            children.sort_by(|a, b| distance(*a).total_cmp(&distance(*b)));
+
+            for pair in children.windows(2) {
+                let (inner, outer) = (pair[0], pair[1]);
+                if distance(inner) + radius(inner) > distance(outer) - radius(outer) {
+                    overlapping.push((inner, outer));
+                }
+            }
+            // the Sun's own surface counts as the innermost path
+            if let Some(&innermost) = children.first().filter(|_| parent == Self::Sun) {
+                if distance(innermost) - radius(innermost) < radius(parent) {
+                    overlapping.push((parent, innermost));
+                }
+            }
+        }
 
-            Self::Mars => 6.4171e23,
-            Self::Phobos => 1.0659e16,
-            Self::Deimos => 1.4762e15,
+        overlapping
+    }
 
-            Self::Jupiter => 1.8982e27,
-            Self::Metis => 1.2e17,
-            Self::Adrastea => 2.2e18,
-            Self::Amalthea => 2.08e18,
-            Self::Thebe => 4.3e19,
-            Self::Io => 8.931938e22,
+    /// The shape of the orbit as drawn in the scene, around what it orbits.
+    /// For planets, the semi-major axis is [`Self::scaled_distance`].
+    pub fn orbital_elements(
+        self,
+        db: &BodyDatabase,
+        exaggeration: &ExaggerationSettings,
+    ) -> OrbitalElements {
+        OrbitalElements {
+            semi_major_axis: self.display_distance(db, exaggeration),
+            eccentricity: self.eccentricity(db),
+            plane: self.orbital_plane(db),
+        }
+    }
 
-            Self::Saturn => 5.6834e26,
-            Self::Enceladus => 1.08e20,
-            Self::Mimas => 3.75e19,
-            Self::Tethys => 6.17449e20,
-            Self::Dione => 1.095452e21,
-            Self::Rhea => 2.306518e21,
-            Self::Titan => 1.3452e23,
+    /// Where the object is from what it orbits in the scene, `mean_anomaly` radians round from
+    /// its closest point. The mean anomaly grows steadily with time, so the object speeds up near
+    /// its closest point and slows down far away, like Kepler's second law says.
+    /// Moons follow an ellipse the size of [`Self::scaled_distance`], pushed out from their
+    /// planet's surface like [`Self::display_distance`].
+    pub fn orbit_position(
+        self,
+        mean_anomaly: f32,
+        db: &BodyDatabase,
+        exaggeration: &ExaggerationSettings,
+    ) -> Vec3 {
+        let eccentricity = self.eccentricity(db);
+        let anomaly = eccentric_anomaly(mean_anomaly, eccentricity);
+        let (sin, cos) = anomaly.sin_cos();
+
+        let semi_major_axis = self.scaled_distance(db, exaggeration);
+        let semi_minor_axis = semi_major_axis * (1.0 - eccentricity * eccentricity).sqrt();
+        // rotating about +Y turns +X towards -Z, which is the way a positive velocity goes round
+        let from_focus = Vec3::new(
+            semi_major_axis * (cos - eccentricity),
+            0.0,
+            -semi_minor_axis * sin,
+        );
+        let pushed_out = from_focus.normalize_or_zero()
+            * (self.display_distance(db, exaggeration) - semi_major_axis);
+
+        self.orbital_plane(db) * (from_focus + pushed_out)
+    }
 
-            Self::Uranus => 8.68103e25,
-            Self::Miranda => 6.59e19,
-            Self::Ariel => 1.353e21,
-            Self::Umbriel => 1.172e21,
-            Self::Titania => 3.49e21,
-            Self::Oberon => 3.014e21,
+    /// Where the object starts out in the scene, at its closest point to what it orbits.
+    pub fn start_position(self, db: &BodyDatabase, exaggeration: &ExaggerationSettings) -> Vec3 {
+        let parent = self.orbits(db);
+        let parent_position = if parent == Self::Sun {
+            Vec3::ZERO
+        } else {
+            parent.orbit_position(0.0, db, exaggeration)
+        };
+        parent_position + self.orbit_position(0.0, db, exaggeration)
+    }
 
-            Self::Neptune => 1.0241e26,
-            Self::Triton => 2.14e22,
-            Self::Nereid => 3.1e19,
-            Self::Proteus => 5.37e19,
-            Self::Larissa => 4.2e18,
-            Self::Halimede => 4.0e18,
+    /// How long `au` astronomical units are in the scene, exaggerated like [`Self::scaled_distance`].
+    pub fn scaled_au(au: f32, exaggeration: &ExaggerationSettings) -> f32 {
+        au * SCENE_UNITS_PER_AU * exaggeration.distance
+    }
 
-            Self::Pluto => 1.303e22,
-            Self::Charon => 1.586e21,
-            Self::Nix => 4.5e16,
-            Self::Hydra => 4.2e16,
-            Self::Kerberos => 1.65e16,
-            Self::Styx => 7.5e15,
-        }
+    /// Turns a distance in the scene back into kilometers, undoing [`Self::scaled_distance`].
+    pub fn unscaled_km(scaled: f32, exaggeration: &ExaggerationSettings) -> f32 {
+        scaled / (SCENE_UNITS_PER_AU * exaggeration.distance) * ASTRO_UNIT
     }
 
-    /// The average orbital velocity in meters per second around [`Self::orbits`].
-    pub fn orbital_velocity(self) -> f32 {
+    // basic information to display on the planet info screen
+
+    /// The number of moons orbiting this planet that are in the scene, counted from
+    /// [`Self::moons`]. The Sun has no moons.
+    /// Most planets have far more, see [`Self::number_of_known_moons`].
+    pub fn num_moons(self, db: &BodyDatabase) -> usize {
         if self == Self::Sun {
+            return 0;
+        }
+        self.moons(db).count()
+    }
+
+    /// Every object, sorted by how far it is from the Sun along its chain of orbits.
+    /// Moons come right after their planet, since they're only a little further out than it.
+    pub fn all_in_orbit_order(db: &BodyDatabase) -> Vec<Self> {
+        let mut all: Vec<Self> = enum_iterator::all::<Self>().collect();
+        all.sort_by(|a, b| a.distance_from_sun(db).total_cmp(&b.distance_from_sun(db)));
+        all
+    }
+
+    /// Everything that orbits this object directly, in declaration order.
+    /// For the Sun, that's the planets (and Pluto).
+    pub fn moons(self, db: &BodyDatabase) -> impl Iterator<Item = Self> + '_ {
+        enum_iterator::all::<Self>().filter(move |obj| *obj != self && obj.orbits(db) == self)
+    }
+
+    /// The strength of the magnetic field at the surface, relative to Earth's.
+    /// Zero means there's no global field, and [`None`] means it hasn't been measured.
+    pub fn magnetic_field_strength(self, db: &BodyDatabase) -> Option<f32> {
+        self.data(db).magnetic_field_strength
+    }
+
+    /// The number of distinct rings around the object.
+    pub fn number_of_rings(self, db: &BodyDatabase) -> usize {
+        self.data(db).number_of_rings
+    }
+
+    /// Whether the object has rings that are drawn in the scene,
+    /// and how far they reach as multiples of the object's radius.
+    pub fn rings(self, db: &BodyDatabase) -> Option<(f32, f32)> {
+        self.data(db).rings
+    }
+
+    /// Whether the object has rings drawn in the scene.
+    pub fn has_rings(self, db: &BodyDatabase) -> bool {
+        self.rings(db).is_some()
+    }
+
+    /// The number of moons the IAU currently recognizes around this object,
+    /// most of which aren't modeled here.
+    pub fn number_of_known_moons(self, db: &BodyDatabase) -> usize {
+        self.data(db).number_of_known_moons
+    }
+
+    /// The average temperature of the planet in Celsius.
+    pub fn temperature(self, db: &BodyDatabase) -> f32 {
+        self.data(db).temperature
+    }
+
+    /// The average temperature of the planet in Kelvin.
+    pub fn temperature_kelvin(self, db: &BodyDatabase) -> f32 {
+        self.temperature(db) + 273.15
+    }
+
+    /// How long it takes to go once around [`Self::orbits`], in Earth days.
+    /// The Sun has no period of revolution.
+    pub fn period_of_revolution(self, db: &BodyDatabase) -> f32 {
+        self.data(db).period_of_revolution
+    }
+
+    /// The period of rotation in Earth days.
+    /// Negative for objects that spin backwards compared to their north pole.
+    pub fn period_of_rotation(self, db: &BodyDatabase) -> f32 {
+        self.data(db).period_of_rotation
+    }
+
+    /// How many simulated seconds an Earth day takes, going by how long Earth's year takes in the
+    /// scene, so spins and orbits run on the same clock.
+    pub fn simulated_seconds_per_day(db: &BodyDatabase) -> f32 {
+        std::f32::consts::TAU
+            / Self::Earth.orbital_velocity(db)
+            / Self::Earth.period_of_revolution(db)
+    }
+
+    /// How far it has turned about its own pole after `elapsed` simulated seconds, in radians
+    /// within one turn. Anticlockwise seen from above its north pole, unless its period is
+    /// negative.
+    pub fn spin_angle(self, elapsed: f64, db: &BodyDatabase) -> f32 {
+        let period =
+            self.period_of_rotation(db) as f64 * Self::simulated_seconds_per_day(db) as f64;
+        if period == 0.0 {
             return 0.0;
         }
+        (std::f64::consts::TAU * elapsed / period).rem_euclid(std::f64::consts::TAU) as f32
+    }
 
-        let distance_from = self.distance() * self.orbits().radius() * ASTRO_UNIT + self.radius();
-        (GRAV * self.orbits().mass() / distance_from).sqrt() / 10_000.0
+    /// The tilt of the rotation axis from the pole of its orbit, in degrees.
+    /// Anything over 90° spins backwards, like Venus, and Uranus is nearly on its side.
+    pub fn axial_tilt(self, db: &BodyDatabase) -> f32 {
+        self.data(db).axial_tilt
     }
 
-    /// The object that this object orbits.
-    /// The Sun orbits itself.
-    pub fn orbits(self) -> Self {
-        match self {
-            Self::Sun => Self::Sun,
-
-            Self::Mercury => Self::Sun,
-
-            Self::Venus => Self::Sun,
-
-            Self::Earth => Self::Sun,
-            Self::EarthMoon => Self::Earth,
-
-            Self::Mars => Self::Sun,
-            Self::Phobos => Self::Mars,
-            Self::Deimos => Self::Mars,
-
-            Self::Jupiter => Self::Sun,
-            Self::Metis => Self::Jupiter,
-            Self::Adrastea => Self::Jupiter,
-            Self::Amalthea => Self::Jupiter,
-            Self::Thebe => Self::Jupiter,
-            Self::Io => Self::Jupiter,
-
-            Self::Saturn => Self::Sun,
-            Self::Enceladus => Self::Saturn,
-            Self::Mimas => Self::Saturn,
-            Self::Tethys => Self::Saturn,
-            Self::Dione => Self::Saturn,
-            Self::Rhea => Self::Saturn,
-            Self::Titan => Self::Saturn,
-
-            Self::Uranus => Self::Sun,
-            Self::Miranda => Self::Uranus,
-            Self::Ariel => Self::Uranus,
-            Self::Umbriel => Self::Uranus,
-            Self::Titania => Self::Uranus,
-            Self::Oberon => Self::Uranus,
-
-            Self::Neptune => Self::Sun,
-            Self::Triton => Self::Neptune,
-            Self::Nereid => Self::Neptune,
-            Self::Proteus => Self::Neptune,
-            Self::Larissa => Self::Neptune,
-            Self::Halimede => Self::Neptune,
-
-            Self::Pluto => Self::Sun,
-            Self::Charon => Self::Pluto,
-            Self::Nix => Self::Pluto,
-            Self::Hydra => Self::Pluto,
-            Self::Kerberos => Self::Pluto,
-            Self::Styx => Self::Pluto,
-        }
-    }
-
-    /// Returns a constant fun fact about the given [`SpaceObject`].
-    pub fn fun_fact(&self) -> &'static str {
-        match self {
-            SpaceObject::Sun => "The Sun is so big that about 1.3 million Earths could fit inside it!",
-            SpaceObject::Mercury => "Mercury is the smallest planet in the Solar System, and it also has the most craters!",
-            SpaceObject::Venus => "Venus is the hottest planet in the Solar System, with surface temperatures that can melt lead!",
-            SpaceObject::Earth => "Earth is the only planet known to have liquid water on its surface, and it's also the only planet known to support life!",
-            SpaceObject::EarthMoon => "The Moon is moving away from Earth at around 3.8 cm per year!",
-            SpaceObject::Mars => "Mars is often called the \"Red Planet\" because of its reddish appearance, caused by iron oxide or rust on its surface!",
-            SpaceObject::Phobos => "Phobos is the larger of Mars' two moons, and it's so close to the planet that it will eventually be torn apart by tidal forces!",
-            SpaceObject::Deimos => "Deimos is the smaller of Mars' two moons, and it's named after the Greek god of terror!",
-            SpaceObject::Jupiter => "Jupiter is the largest planet in the Solar System, and it also has the strongest magnetic field of any planet!",
-            SpaceObject::Io => "Io is one of Jupiter's four largest moons, and it's the most volcanically active object in the Solar System!",
-            SpaceObject::Saturn => "Saturn is famous for its beautiful rings, which are made up of ice and rock particles ranging in size from tiny grains to massive chunks!",
-            SpaceObject::Uranus => "Uranus is the only planet in the Solar System that rotates on its side, with its north and south poles pointing almost directly at the Sun!",
-            SpaceObject::Neptune => "Neptune is the farthest planet from the Sun, and it has the strongest winds of any planet in the Solar System, with gusts that can reach up to 1,200 miles per hour!",
-            _ => "Sorry, no fun fact available for this planet yet!",
-        }
-    }
-
-    /// The scale of the planet relative to the Sun.
-    /// The Sun has a radius of 695,700 km.
-    pub fn scaled_radius(self) -> f32 {
-        if self == Self::Sun {
-            self.radius() / 100.0
-        } else {
-            self.radius() / (Self::Sun.radius() / 100_000.0) // just make it a bit bigger
+    /// How much of the sunlight hitting it is reflected back, seen face on.
+    pub fn albedo(self, db: &BodyDatabase) -> f32 {
+        self.data(db).albedo
+    }
+
+    /// The direction of the north pole in the scene, the one it spins anticlockwise around.
+    /// The tilt leans away from the line of nodes, so it stays fixed as the object orbits.
+    /// Like [`Self::orbital_plane`], moons lean from their planet's equator.
+    pub fn rotation_axis(self, db: &BodyDatabase) -> Vec3 {
+        self.reference_plane(db)
+            * Quat::from_rotation_x((self.inclination(db) + self.axial_tilt(db)).to_radians())
+            * Vec3::Y
+    }
+
+    /// The rotation that stands a sphere mesh, which is built with its north pole along Z, up
+    /// along [`Self::rotation_axis`]. Only the orbit moves an object, so its poles stay put.
+    pub fn pole_orientation(self, db: &BodyDatabase) -> Quat {
+        self.reference_plane(db)
+            * Quat::from_rotation_x(
+                (self.inclination(db) + self.axial_tilt(db) - 90.0).to_radians(),
+            )
+    }
+
+    /// Finds when this object and `other` are next closest, starting from their current mean
+    /// anomalies (see [`Self::orbit_position`]) and stepping both along their real orbits over one
+    /// synodic period. Returns [`None`] unless both are distinct objects orbiting the Sun.
+    pub fn closest_approach(
+        self,
+        mean_anomaly: f32,
+        other: Self,
+        other_mean_anomaly: f32,
+        db: &BodyDatabase,
+    ) -> Option<Approach> {
+        if self == other
+            || self == Self::Sun
+            || other == Self::Sun
+            || self.orbits(db) != Self::Sun
+            || other.orbits(db) != Self::Sun
+        {
+            return None;
         }
+
+        let (period, other_period) = (
+            self.period_of_revolution(db),
+            other.period_of_revolution(db),
+        );
+        let synodic_period = 1.0 / (1.0 / period - 1.0 / other_period).abs();
+        let step = (synodic_period / 10_000.0).max(1.0);
+
+        // mean anomalies count down for retrograde orbits, like in the simulation
+        let position = |obj: Self, mean_anomaly: f32, period: f32, days: f32| {
+            let turned = std::f32::consts::TAU * days / period;
+            let direction = obj.orbital_velocity(db).signum();
+            obj.orbit_position(
+                mean_anomaly + direction * turned,
+                db,
+                &ExaggerationSettings::REALISTIC,
+            ) / SCENE_UNITS_PER_AU
+        };
+        let separation = |days: f32| {
+            position(self, mean_anomaly, period, days).distance(position(
+                other,
+                other_mean_anomaly,
+                other_period,
+                days,
+            ))
+        };
+
+        let mut closest = Approach {
+            distance: separation(0.0),
+            days: 0.0,
+        };
+        let mut days = step;
+        while days <= synodic_period {
+            let distance = separation(days);
+            if distance < closest.distance {
+                closest = Approach { distance, days };
+            }
+            days += step;
+        }
+
+        Some(closest)
     }
 
-    /// The distance from the Sun.
-    pub fn scaled_distance(self) -> f32 {
-        self.distance() * (Self::Sun.radius() / 10.0)
+    /// Objects orbiting the same thing as this one whose orbital periods are within 1% of a
+    /// small whole-number ratio with this one's, like Pluto going around the Sun twice for
+    /// every three times Neptune does.
+    pub fn resonances(self, db: &BodyDatabase) -> Vec<Resonance> {
+        const MAX_TERM: u32 = 5;
+        const TOLERANCE: f32 = 0.01;
+
+        let period = self.period_of_revolution(db);
+        if self == Self::Sun || period <= 0.0 {
+            return Vec::new();
+        }
+
+        enum_iterator::all::<Self>()
+            .filter(|other| {
+                *other != self && *other != Self::Sun && other.orbits(db) == self.orbits(db)
+            })
+            .filter_map(|other| {
+                let other_period = other.period_of_revolution(db);
+                if other_period <= 0.0 {
+                    return None;
+                }
+
+                let ratio = period.max(other_period) / period.min(other_period);
+                // the simplest ratio wins, since (4, 2) would also match wherever (2, 1) does
+                (1..=MAX_TERM)
+                    .flat_map(|q| (q + 1..=MAX_TERM).map(move |p| (p, q)))
+                    .filter(|(p, q)| {
+                        let exact = *p as f32 / *q as f32;
+                        (ratio - exact).abs() / exact < TOLERANCE
+                    })
+                    .min_by_key(|(p, q)| p + q)
+                    .map(|ratio| Resonance { with: other, ratio })
+            })
+            .collect()
     }
+}
 
-    // basic information to display on the planet info screen
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_object_has_positive_size_and_mass() {
+        let db = BodyDatabase::embedded();
+        for obj in enum_iterator::all::<SpaceObject>() {
+            assert!(obj.radius(&db) > 0.0, "{obj:?}");
+            assert!(obj.mass(&db) > 0.0, "{obj:?}");
+
+            let exaggeration = ExaggerationSettings::default();
+            let radius = obj.scaled_radius(&db, &exaggeration);
+            let distance = obj.scaled_distance(&db, &exaggeration);
+            assert!(radius.is_finite() && radius >= 0.0, "{obj:?}");
+            assert!(distance.is_finite() && distance >= 0.0, "{obj:?}");
+        }
+    }
 
-    /// The number of moons orbiting this planet.
-    /// The Sun has no moons.
-    pub fn num_moons(self) -> usize {
-        match self {
-            Self::Sun => 0,
-            Self::Mercury => 0,
-            Self::Venus => 0,
-            Self::Earth => 1,
-            Self::Mars => 2,
-            Self::Jupiter => 79,
-            Self::Saturn => 62,
-            Self::Uranus => 27,
-            Self::Neptune => 14,
-            Self::Pluto => 5,
-            _ => 0,
+    #[test]
+    fn every_orbit_leads_to_the_sun() {
+        let db = BodyDatabase::embedded();
+        assert_eq!(SpaceObject::Sun.orbits(&db), SpaceObject::Sun);
+
+        for obj in enum_iterator::all::<SpaceObject>() {
+            let mut parent = obj;
+            for _ in enum_iterator::all::<SpaceObject>() {
+                parent = parent.orbits(&db);
+            }
+            assert_eq!(parent, SpaceObject::Sun, "{obj:?}");
         }
     }
 
-    /// The average temperature of the planet in Celsius.
-    pub fn temperature(self) -> f32 {
-        match self {
-            Self::Sun => 5778.0,
-            Self::Mercury => 167.0,
-            Self::Venus => 464.0,
-            Self::Earth => 15.0,
-            Self::EarthMoon => -20.0,
-            Self::Mars => -63.0,
-            Self::Phobos => -58.0,
-            Self::Deimos => -40.0,
-            Self::Jupiter => -108.0,
-            Self::Metis => -100.0,
-            Self::Adrastea => -100.0,
-            Self::Amalthea => -100.0,
-            Self::Thebe => -100.0,
-            Self::Io => -143.0,
-            Self::Saturn => -139.0,
-            Self::Enceladus => -198.0,
-            Self::Mimas => -201.0,
-            Self::Tethys => -187.0,
-            Self::Dione => -186.0,
-            Self::Rhea => -196.0,
-            Self::Titan => -179.0,
-            Self::Uranus => -197.0,
-            Self::Miranda => -187.0,
-            Self::Ariel => -191.0,
-            Self::Umbriel => -200.0,
-            Self::Titania => -195.0,
-            Self::Oberon => -197.0,
-            Self::Neptune => -201.0,
-            Self::Triton => -235.0,
-            Self::Nereid => -220.0,
-            Self::Proteus => -200.0,
-            Self::Larissa => -197.0,
-            Self::Halimede => -215.0,
-            Self::Pluto => -229.0,
-            Self::Charon => -229.0,
-            Self::Nix => -230.0,
-            Self::Hydra => -232.0,
-            Self::Kerberos => -233.0,
-            Self::Styx => -233.0,
-        }
-    }
-
-    /// The period of revolution around the Sun in Earth days.
-    /// The Sun has no period of revolution.
-    pub fn period_of_revolution(self) -> f32 {
-        match self {
-            Self::Mercury => 88.0,
-            Self::Venus => 225.0,
-            Self::Earth => 365.0,
-            Self::Mars => 687.0,
-            Self::Jupiter => 4333.0,
-            Self::Saturn => 10759.0,
-            Self::Uranus => 30687.0,
-            Self::Neptune => 60190.0,
-            Self::Pluto => 90560.0,
-            _ => 0.0,
+    #[test]
+    fn moons_orbit_planets() {
+        let db = BodyDatabase::embedded();
+        for moon in enum_iterator::all::<SpaceObject>().filter(|o| o.category() == Category::Moon) {
+            let parent = moon.orbits(&db).category();
+            assert!(
+                parent != Category::Moon && parent != Category::Star,
+                "{moon:?} orbits a {parent:?}"
+            );
         }
     }
 
-    /// The period of rotation in Earth days.
-    pub fn period_of_rotation(self) -> f32 {
-        match self {
-            Self::Sun => 25.38,
-            Self::Mercury => 58.65,
-            Self::Venus => 243.02,
-            Self::Earth => 1.00,
-            Self::EarthMoon => 27.32,
-            Self::Mars => 1.03,
-            Self::Phobos => 0.32,
-            Self::Deimos => 1.26,
-            Self::Jupiter => 0.41,
-            Self::Metis => 0.30,
-            Self::Adrastea => 0.30,
-            Self::Amalthea => 0.50,
-            Self::Thebe => 0.67,
-            Self::Io => 1.77,
-            Self::Saturn => 0.44,
-            Self::Mimas => 0.94,
-            Self::Enceladus => 1.37,
-            Self::Tethys => 1.89,
-            Self::Dione => 2.74,
-            Self::Rhea => 4.52,
-            Self::Titan => 15.95,
-            Self::Uranus => 0.72,
-            Self::Miranda => 1.41,
-            Self::Ariel => 2.52,
-            Self::Umbriel => 4.14,
-            Self::Titania => 8.71,
-            Self::Oberon => 13.46,
-            Self::Neptune => 0.67,
-            Self::Triton => -5.88,
-            Self::Nereid => 360.13,
-            Self::Proteus => 1.12,
-            Self::Larissa => 0.55,
-            Self::Halimede => 0.43,
-            Self::Pluto => 6.39,
-            Self::Charon => 6.39,
-            Self::Nix => 1.83,
-            Self::Hydra => 1.38,
-            Self::Kerberos => 5.31,
-            Self::Styx => 20.16,
+    #[test]
+    fn periapsis_is_no_further_than_apoapsis() {
+        let db = BodyDatabase::embedded();
+        let exaggeration = ExaggerationSettings::REALISTIC;
+        for obj in enum_iterator::all::<SpaceObject>() {
+            let periapsis = obj.orbit_position(0.0, &db, &exaggeration).length();
+            let apoapsis = obj
+                .orbit_position(std::f32::consts::PI, &db, &exaggeration)
+                .length();
+            assert!(periapsis <= apoapsis, "{obj:?}");
+        }
+    }
+
+    // neighbours drawn on top of each other, like after adding a moon or changing a scale
+    #[test]
+    fn no_orbits_overlap() {
+        let db = BodyDatabase::embedded();
+        for exaggeration in [
+            ExaggerationSettings::DIAGRAM,
+            ExaggerationSettings::REALISTIC,
+        ] {
+            assert_eq!(
+                SpaceObject::overlapping_orbits(&db, &exaggeration),
+                vec![],
+                "at {exaggeration:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn trivia_makes_sense_for_every_object() {
+        let db = BodyDatabase::embedded();
+        for obj in enum_iterator::all::<SpaceObject>() {
+            if let Some(strength) = obj.magnetic_field_strength(&db) {
+                assert!(strength.is_finite() && strength >= 0.0, "{obj:?}");
+            }
+            if obj.has_rings(&db) {
+                assert!(obj.number_of_rings(&db) > 0, "{obj:?}");
+            }
+            assert!(
+                obj.number_of_known_moons(&db) >= obj.num_moons(&db),
+                "{obj:?} has more moons in the scene than are known"
+            );
+        }
+    }
+
+    #[test]
+    fn moons_are_about_as_far_from_the_sun_as_their_planet() {
+        let db = BodyDatabase::embedded();
+        for moon in enum_iterator::all::<SpaceObject>().filter(|o| o.category() == Category::Moon) {
+            let planet = moon.orbits(&db).distance_from_sun(&db);
+            let distance = moon.distance_from_sun(&db);
+            assert!(distance > planet, "{moon:?}");
+            assert!((distance - planet) / planet < 0.01, "{moon:?}");
+        }
+    }
+
+    #[test]
+    fn embedded_database_covers_every_object() {
+        let db = BodyDatabase::embedded();
+        for obj in enum_iterator::all::<SpaceObject>() {
+            assert!(db.0.contains_key(&obj), "{obj:?}");
+        }
+        assert_eq!(db.0.len(), enum_iterator::cardinality::<SpaceObject>());
+    }
+
+    #[test]
+    fn orbit_order_goes_out_from_the_sun() {
+        use SpaceObject::*;
+
+        let db = BodyDatabase::embedded();
+        let order = SpaceObject::all_in_orbit_order(&db);
+        assert_eq!(order.first(), Some(&Sun));
+
+        let planets: Vec<_> = order
+            .iter()
+            .copied()
+            .filter(|obj| *obj != Sun && obj.orbits(&db) == Sun)
+            .collect();
+        assert_eq!(
+            planets,
+            [Mercury, Venus, Earth, Mars, Jupiter, Saturn, Uranus, Neptune, Pluto]
+        );
+
+        // each moon comes after its planet, before the next planet out
+        for (i, moon) in order.iter().enumerate() {
+            let parent = moon.orbits(&db);
+            if parent == Sun {
+                continue;
+            }
+            let planet = order.iter().position(|obj| *obj == parent).unwrap();
+            assert!(planet < i, "{moon:?}");
+            assert!(
+                order[planet + 1..i]
+                    .iter()
+                    .all(|obj| obj.orbits(&db) == parent),
+                "{moon:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn mercury_is_closest_at_periapsis() {
+        let db = BodyDatabase::embedded();
+        let exaggeration = ExaggerationSettings::REALISTIC;
+        let mercury = SpaceObject::Mercury;
+        let eccentricity = mercury.eccentricity(&db);
+
+        let periapsis = mercury.orbit_position(0.0, &db, &exaggeration).length();
+        let apoapsis = mercury
+            .orbit_position(std::f32::consts::PI, &db, &exaggeration)
+            .length();
+        let expected = (1.0 - eccentricity) / (1.0 + eccentricity);
+        assert!((periapsis / apoapsis - expected).abs() < 1e-3);
+        assert!((eccentricity - 0.2056).abs() < 0.01);
+    }
+
+    #[test]
+    fn moons_come_from_the_table() {
+        let db = BodyDatabase::embedded();
+        let mars: Vec<_> = SpaceObject::Mars.moons(&db).collect();
+        assert_eq!(mars, [SpaceObject::Phobos, SpaceObject::Deimos]);
+        assert_eq!(SpaceObject::Mars.num_moons(&db), 2);
+        assert_eq!(SpaceObject::Earth.num_moons(&db), 1);
+        assert_eq!(SpaceObject::Venus.num_moons(&db), 0);
+        assert_eq!(SpaceObject::Sun.num_moons(&db), 0);
+    }
+
+    #[test]
+    fn the_moon_goes_round_in_about_27_days() {
+        let db = BodyDatabase::embedded();
+        let period = SpaceObject::EarthMoon.period_of_revolution(&db);
+        assert!((period / 27.3 - 1.0).abs() < 0.03, "{period}");
+
+        for moon in enum_iterator::all::<SpaceObject>().filter(|o| o.category() == Category::Moon) {
+            assert!(moon.period_of_revolution(&db) > 0.0, "{moon:?}");
+        }
+    }
+
+    #[test]
+    fn earths_gravity_and_escape_velocity() {
+        let db = BodyDatabase::embedded();
+        let gravity = SpaceObject::Earth.surface_gravity(&db);
+        assert!((gravity - 9.8).abs() < 0.1, "{gravity}");
+        // in meters per second
+        let escape = SpaceObject::Earth.escape_velocity(&db);
+        assert!((escape / 1_000.0 - 11.2).abs() < 0.1, "{escape}");
+    }
+
+    #[test]
+    fn saturn_would_float() {
+        let db = BodyDatabase::embedded();
+        let earth = SpaceObject::Earth.density(&db);
+        assert!((earth - 5_500.0).abs() < 100.0, "{earth}");
+        let saturn = SpaceObject::Saturn.density(&db);
+        assert!(saturn < 1_000.0, "{saturn}");
+    }
+
+    #[test]
+    fn untilted_moons_spin_about_their_orbit_normal() {
+        let mut db = BodyDatabase::embedded();
+        let moons: Vec<_> = enum_iterator::all::<SpaceObject>()
+            .filter(|o| o.category() == Category::Moon)
+            .collect();
+        for moon in &moons {
+            db.0.get_mut(moon).unwrap().axial_tilt = 0.0;
+        }
+
+        for moon in moons {
+            let normal = moon.orbital_plane(&db) * Vec3::Y;
+            let axis = moon.rotation_axis(&db);
+            // retrograde orbits go round the other way, so their normal points down
+            assert!(axis.dot(normal).abs() > 0.9999, "{moon:?}");
+            assert!(
+                (moon.pole_orientation(&db) * Vec3::Z).dot(axis) > 0.9999,
+                "{moon:?}"
+            );
         }
+
+        // Uranus lies on its side, and so do its moons
+        let uranus = SpaceObject::Uranus.rotation_axis(&db);
+        assert!(SpaceObject::Miranda.rotation_axis(&db).dot(uranus) > 0.99);
+    }
+
+    #[test]
+    fn moon_distance_in_kilometers() {
+        let db = BodyDatabase::embedded();
+        let km = SpaceObject::EarthMoon.distance_from_parent_km(&db);
+        assert!((km - 384_400.0).abs() < 1_000.0, "{km}");
     }
 }
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    selection::CurrentObject,
+    space::{BodyDatabase, SpaceObject},
+    with_separators,
+};
+
+/// What the bars in the comparison window measure.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    #[default]
+    Speed,
+    Period,
+    Distance,
+}
+
+impl Metric {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Speed => "Orbital speed",
+            Self::Period => "Orbital period",
+            Self::Distance => "Distance",
+        }
+    }
+
+    fn value(self, obj: SpaceObject, db: &BodyDatabase) -> f32 {
+        match self {
+            Self::Speed => obj.orbital_speed(db),
+            Self::Period => obj.period_of_revolution(db),
+            Self::Distance => obj.distance_from_parent(db),
+        }
+    }
+
+    fn format(self, value: f32) -> String {
+        match self {
+            Self::Speed => format!("{value:.1} km/s"),
+            Self::Period => format!("{} days", with_separators(value.round())),
+            Self::Distance => format!("{value:.2} AU"),
+        }
+    }
+}
+
+/// How wide the longest bar is, in points.
+const BAR_WIDTH: f32 = 160.0;
+
+// a bar chart of every planet side by side, to show how much faster the inner ones move
+pub fn speeds_ui(
+    mut egui_ctx: ResMut<EguiContext>,
+    db: Res<BodyDatabase>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+    mut metric: Local<Metric>,
+) {
+    let selected = current.get_single().ok().copied();
+    let planets: Vec<SpaceObject> = SpaceObject::all_in_orbit_order(&db)
+        .into_iter()
+        .filter(|obj| obj.orbits(&db) == SpaceObject::Sun && *obj != SpaceObject::Sun)
+        .collect();
+    let max = planets
+        .iter()
+        .map(|obj| metric.value(*obj, &db))
+        .fold(0.0, f32::max);
+
+    egui::Window::new("Compare orbits")
+        .default_open(false)
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                for option in [Metric::Speed, Metric::Period, Metric::Distance] {
+                    ui.selectable_value(&mut *metric, option, option.name());
+                }
+            });
+            ui.separator();
+
+            egui::Grid::new("orbit comparison").show(ui, |ui| {
+                for obj in &planets {
+                    let value = metric.value(*obj, &db);
+                    ui.label(obj.name(&db));
+
+                    let (rect, _) =
+                        ui.allocate_exact_size(egui::vec2(BAR_WIDTH, 12.0), egui::Sense::hover());
+                    let mut bar = rect;
+                    bar.set_width(if max > 0.0 {
+                        BAR_WIDTH * value / max
+                    } else {
+                        0.0
+                    });
+                    let color = if selected == Some(*obj) {
+                        egui::Color32::GOLD
+                    } else {
+                        egui::Color32::from_rgb(90, 140, 200)
+                    };
+                    ui.painter().rect_filled(bar, 2.0, color);
+
+                    ui.label(metric.format(value));
+                    ui.end_row();
+                }
+            });
+        });
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+use crate::{
+    asteroids::Asteroid,
+    habitable::HabitableZone,
+    orbits::OrbitPath,
+    selection::CurrentObject,
+    space::{BodyDatabase, SpaceObject},
+};
+
+/// Whether everything but the selected object and its close neighbours is hidden while something
+/// is selected, to keep an audience looking at one world.
+#[derive(Resource, Default)]
+pub struct Spotlight(pub bool);
+
+// `subject` keeps what orbits it and what it orbits in view. The Sun always stays, since hiding
+// it would put its light out too
+fn in_spotlight(obj: SpaceObject, subject: SpaceObject, db: &BodyDatabase) -> bool {
+    obj == subject
+        || obj == SpaceObject::Sun
+        || obj.orbits(db) == subject
+        || subject.orbits(db) == obj
+}
+
+// hide or show everything for the current selection, restoring it all once the spotlight is off
+// or nothing is selected. Checked every frame, since orbits are redrawn with new entities
+pub fn apply_spotlight(
+    spotlight: Res<Spotlight>,
+    db: Res<BodyDatabase>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+    mut objs: Query<(&SpaceObject, &mut Visibility)>,
+    mut clutter: Query<
+        &mut Visibility,
+        (
+            Or<(With<OrbitPath>, With<HabitableZone>, With<Asteroid>)>,
+            Without<SpaceObject>,
+        ),
+    >,
+) {
+    let subject = current.get_single().ok().filter(|_| spotlight.0);
+
+    for (obj, mut visibility) in objs.iter_mut() {
+        let visible = subject.map_or(true, |subject| in_spotlight(*obj, *subject, &db));
+        if visibility.is_visible != visible {
+            visibility.is_visible = visible;
+        }
+    }
+    for mut visibility in clutter.iter_mut() {
+        let visible = subject.is_none();
+        if visibility.is_visible != visible {
+            visibility.is_visible = visible;
+        }
+    }
+}
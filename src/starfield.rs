@@ -0,0 +1,110 @@
+use bevy::{
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+};
+
+use crate::RenderStyle;
+
+/// Marks the sphere the stars are painted on the inside of. It's only shown in
+/// [`RenderStyle::Textured`], see [`apply_render_style`].
+#[derive(Component)]
+pub struct Starfield;
+
+/// How far out the stars are drawn, as a fraction of the camera's far plane, so they're always
+/// behind everything else but never clipped.
+const STARFIELD_DISTANCE: f32 = 0.9;
+
+/// How many segments go round the sphere. The stars are all in the texture, so this only needs
+/// to be round enough not to show corners, and less so on the web.
+#[cfg(not(target_arch = "wasm32"))]
+const SECTORS: usize = 64;
+#[cfg(target_arch = "wasm32")]
+const SECTORS: usize = 24;
+
+// a sphere around the camera with the sky photo inside it. It's unlit so the Sun's light doesn't
+// shade it, and isn't pickable so clicks go through to empty space
+pub fn spawn_starfield(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::UVSphere {
+                radius: 1.0,
+                sectors: SECTORS,
+                stacks: SECTORS / 2,
+            })),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(asset_server.load("skybox.jpg")),
+                unlit: true,
+                // seen from the inside
+                cull_mode: None,
+                ..default()
+            }),
+            ..default()
+        },
+        NotShadowCaster,
+        NotShadowReceiver,
+        Starfield,
+    ));
+}
+
+// the flat diagram look goes without the photographic sky
+pub fn apply_render_style(
+    style: Res<RenderStyle>,
+    mut starfield: Query<&mut Visibility, With<Starfield>>,
+) {
+    if !style.is_changed() {
+        return;
+    }
+
+    for mut visibility in starfield.iter_mut() {
+        visibility.is_visible = *style == RenderStyle::Textured;
+    }
+}
+
+// keep the stars centered on the camera and just inside its far plane, so they never get closer
+pub fn follow_camera(
+    cameras: Query<(&Transform, &Projection), (With<Camera3d>, Without<Starfield>)>,
+    mut starfield: Query<&mut Transform, With<Starfield>>,
+) {
+    let Ok((camera, projection)) = cameras.get_single() else {
+        return;
+    };
+    let Projection::Perspective(perspective) = projection else {
+        return;
+    };
+
+    let radius = perspective.far * STARFIELD_DISTANCE;
+    for mut transform in starfield.iter_mut() {
+        *transform = Transform {
+            translation: camera.translation,
+            // the sphere's poles are along its local Z, so stand it up. It's flipped in X since
+            // the texture is seen from the inside, which would otherwise mirror it
+            rotation: Quat::from_rotation_x(-90.0_f32.to_radians()),
+            scale: Vec3::new(-radius, radius, radius),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_style_hides_the_stars() {
+        let mut app = App::new();
+        app.insert_resource(RenderStyle::Flat)
+            .add_system(apply_render_style);
+        let starfield = app.world.spawn((Starfield, Visibility::VISIBLE)).id();
+
+        app.update();
+        assert!(!app.world.get::<Visibility>(starfield).unwrap().is_visible);
+
+        *app.world.resource_mut::<RenderStyle>() = RenderStyle::Textured;
+        app.update();
+        assert!(app.world.get::<Visibility>(starfield).unwrap().is_visible);
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::{
+    diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
+    ecs::schedule::ShouldRun,
+    prelude::*,
+};
+use bevy_egui::{egui, EguiContext};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    focus::{PauseWhenUnfocused, WindowFocus},
+    selection::CurrentObject,
+    settings::Persisted,
+    simulation::{simulated_date, simulation_running, Paused, SimSpeed, SimulationClock},
+    space::{BodyDatabase, SpaceObject},
+    AppState,
+};
+
+/// Whether the strip of stats along the bottom of the screen is shown.
+#[derive(Resource, Serialize, Deserialize)]
+pub struct ShowStatsStrip(pub bool);
+
+impl Default for ShowStatsStrip {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+impl Persisted for ShowStatsStrip {
+    const KEY: &'static str = "show_stats_strip";
+}
+
+// a thin bar of the things worth knowing at a glance, instead of a scatter of little labels
+#[allow(clippy::too_many_arguments)]
+pub fn stats_strip(
+    mut egui_ctx: ResMut<EguiContext>,
+    show: Res<ShowStatsStrip>,
+    diagnostics: Res<Diagnostics>,
+    state: Res<State<AppState>>,
+    focus: Res<WindowFocus>,
+    pause_when_unfocused: Res<PauseWhenUnfocused>,
+    paused: Res<Paused>,
+    speed: Res<SimSpeed>,
+    clock: Res<SimulationClock>,
+    db: Res<BodyDatabase>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+) {
+    if !show.0 {
+        return;
+    }
+
+    let running = matches!(
+        simulation_running(state, focus, pause_when_unfocused, paused),
+        ShouldRun::Yes
+    );
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed());
+
+    egui::TopBottomPanel::bottom("stats strip").show(egui_ctx.ctx_mut(), |ui| {
+        ui.horizontal(|ui| {
+            ui.label(if running {
+                format!("×{}", speed.0)
+            } else {
+                "Paused".to_string()
+            });
+            ui.separator();
+            ui.label(simulated_date(clock.shown_days(&db)));
+            ui.separator();
+            ui.label(match current.get_single() {
+                Ok(obj) => format!("Selected: {}", obj.name(&db)),
+                Err(_) => "Nothing selected".to_string(),
+            });
+            if let Some(fps) = fps {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(format!("{fps:.0} fps"));
+                });
+            }
+        });
+    });
+}
@@ -0,0 +1,78 @@
+use bevy::{
+    prelude::*,
+    reflect::TypeUuid,
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+
+use crate::{
+    selection::CurrentObject,
+    space::{BodyDatabase, SpaceObject},
+    SunMaterials,
+};
+
+/// The Sun's surface: its texture with scrolling noise on top to look like churning plasma.
+/// The animation is driven by the `globals.time` uniform Bevy already hands every shader.
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "9c6e5537-6e1a-416d-92be-24d821a2b3be"]
+pub struct SunMaterial {
+    #[texture(0)]
+    #[sampler(1)]
+    pub texture: Handle<Image>,
+    /// Scales how bright the surface is, with 1 being full brightness. Only `x` is read, the
+    /// rest pads it out to the 16 bytes WebGL2 needs a uniform buffer to be at least.
+    #[uniform(2)]
+    pub brightness: Vec4,
+}
+
+impl Material for SunMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "sun.wgsl".into()
+    }
+}
+
+/// Whether the Sun is dimmed while looking at something close to it, so its glare doesn't wash
+/// out the view of Mercury or Venus. Off unless it's turned on in the Effects window.
+#[derive(Resource, Default)]
+pub struct DimSunNearby(pub bool);
+
+/// Objects closer to the Sun than this many AU count as nearby, which is everything inside the
+/// asteroid belt.
+const NEARBY_DISTANCE: f32 = 2.0;
+
+/// How bright the Sun is drawn while it's dimmed.
+const DIMMED_BRIGHTNESS: f32 = 0.3;
+
+// turn the Sun down while a nearby object is selected, and back up once it isn't
+pub fn dim_sun(
+    dim: Res<DimSunNearby>,
+    db: Res<BodyDatabase>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+    sun: Query<&SunMaterials>,
+    mut sun_materials: ResMut<Assets<SunMaterial>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let nearby = current.get_single().map_or(false, |obj| {
+        *obj != SpaceObject::Sun && obj.distance_from_sun(&db) < NEARBY_DISTANCE
+    });
+    let brightness = if dim.0 && nearby {
+        DIMMED_BRIGHTNESS
+    } else {
+        1.0
+    };
+
+    for handles in sun.iter() {
+        // only touch the materials when the brightness changes, since that re-uploads them
+        if sun_materials
+            .get(&handles.textured)
+            .map_or(true, |material| material.brightness.x == brightness)
+        {
+            continue;
+        }
+        if let Some(material) = sun_materials.get_mut(&handles.textured) {
+            material.brightness = Vec4::splat(brightness);
+        }
+        if let Some(material) = materials.get_mut(&handles.flat) {
+            material.base_color = SpaceObject::Sun.category().color() * brightness;
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    space::{BodyDatabase, SpaceObject},
+    ObjectMaterials,
+};
+
+/// Whether objects glow faintly in a color showing how hot or cold they are.
+#[derive(Resource, Default)]
+pub struct TemperatureTint(pub bool);
+
+/// How strong the glow is, kept faint so textures still show through.
+const TINT_STRENGTH: f32 = 0.15;
+
+/// Temperatures in Kelvin and the colors they map to, from the coldest moons to Venus.
+const GRADIENT: [(f32, [f32; 3]); 4] = [
+    (40.0, [0.2, 0.4, 1.0]),
+    (200.0, [0.7, 0.9, 1.0]),
+    (300.0, [1.0, 1.0, 0.85]),
+    (740.0, [1.0, 0.4, 0.1]),
+];
+
+/// A color for a temperature in Kelvin, going from blue for cold to orange for hot.
+pub fn temperature_color(kelvin: f32) -> Color {
+    let (first, last) = (GRADIENT[0], GRADIENT[GRADIENT.len() - 1]);
+    let kelvin = kelvin.clamp(first.0, last.0);
+
+    let [r, g, b] = GRADIENT
+        .windows(2)
+        .find(|stops| kelvin <= stops[1].0)
+        .map_or(last.1, |stops| {
+            let ((from, low), (to, high)) = (stops[0], stops[1]);
+            let t = (kelvin - from) / (to - from);
+            [0, 1, 2].map(|i| low[i] + (high[i] - low[i]) * t)
+        });
+
+    Color::rgb(r, g, b)
+}
+
+pub fn apply_temperature_tint(
+    tint: Res<TemperatureTint>,
+    db: Res<BodyDatabase>,
+    objs: Query<(&SpaceObject, &ObjectMaterials)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !tint.is_changed() || tint.is_added() {
+        return;
+    }
+
+    for (obj, obj_materials) in objs.iter() {
+        if let Some(material) = materials.get_mut(&obj_materials.textured) {
+            material.emissive = if tint.0 {
+                temperature_color(obj.temperature_kelvin(&db)) * TINT_STRENGTH
+            } else {
+                Color::BLACK
+            };
+        }
+    }
+}
+
+// a key for the tint colors, only shown while the tint is on
+pub fn temperature_legend(mut egui_ctx: ResMut<EguiContext>, tint: Res<TemperatureTint>) {
+    if !tint.0 {
+        return;
+    }
+
+    egui::Window::new("Temperature").show(egui_ctx.ctx_mut(), |ui| {
+        for (kelvin, _) in GRADIENT {
+            ui.horizontal(|ui| {
+                let [r, g, b, _] = temperature_color(kelvin).as_rgba_f32();
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+                ui.painter().rect_filled(
+                    rect,
+                    2.0,
+                    egui::Color32::from_rgb(
+                        (r * 255.0) as u8,
+                        (g * 255.0) as u8,
+                        (b * 255.0) as u8,
+                    ),
+                );
+                ui.label(format!("{:.0}°C", kelvin - 273.15));
+            });
+        }
+    });
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    advanced_stats, object_stats,
+    selection::{CurrentObject, Selection},
+    settings::Persisted,
+    space::{BodyDatabase, SpaceObject},
+    units::UnitSystem,
+};
+
+/// Whether to show every object's information as a plain list, for exploring without the 3D
+/// view (like with a screen reader).
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct TextOnlyMode(pub bool);
+
+impl Persisted for TextOnlyMode {
+    const KEY: &'static str = "text_only_mode";
+}
+
+// every object in order, each a heading that opens up to everything known about it,
+// so tabbing through goes object by object
+pub fn text_only_ui(
+    mut selection: Selection,
+    mut egui_ctx: ResMut<EguiContext>,
+    mode: Res<TextOnlyMode>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+    units: Res<UnitSystem>,
+    db: Res<BodyDatabase>,
+) {
+    if !mode.0 {
+        return;
+    }
+    let current = current.get_single().ok().copied();
+
+    egui::Window::new("All objects")
+        .default_width(400.0)
+        .vscroll(true)
+        .show(egui_ctx.ctx_mut(), |ui| {
+            for obj in enum_iterator::all::<SpaceObject>() {
+                let heading = if obj.orbits(&db) == SpaceObject::Sun {
+                    format!("{} ({})", obj.name(&db), obj.category().name())
+                } else {
+                    format!("{} (moon of {})", obj.name(&db), obj.orbits(&db).name(&db))
+                };
+
+                ui.collapsing(heading, |ui| {
+                    if current == Some(obj) {
+                        ui.label("Currently selected");
+                    } else if ui.button(format!("Select {}", obj.name(&db))).clicked() {
+                        selection.select(obj);
+                    }
+                    object_stats(ui, obj, &db, *units);
+                    advanced_stats(ui, obj, &db);
+                    ui.label(format!("Fun fact: {}", obj.fun_fact(&db)));
+                });
+            }
+        });
+}
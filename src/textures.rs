@@ -0,0 +1,193 @@
+use bevy::{asset::LoadState, prelude::*, render::renderer::RenderDevice, utils::HashMap};
+use enum_iterator::Sequence;
+use serde::Deserialize;
+
+use crate::{
+    selection::{object_selected, CurrentObject},
+    space::SpaceObject,
+    sun::SunMaterial,
+    ObjectMaterials, SunMaterials,
+};
+
+/// Drawing objects with the images from the chosen [`TexturePack`], at the [`TextureDetail`]
+/// the GPU and selection allow.
+pub struct TexturesPlugin;
+
+impl Plugin for TexturesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TexturePack>()
+            .init_resource::<TextureDetail>()
+            .init_resource::<TextureFiles>()
+            .add_system(apply_textures.after(object_selected));
+    }
+}
+
+/// Which set of images the textured objects are drawn with.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Sequence)]
+pub enum TexturePack {
+    /// Photos and maps from spacecraft.
+    #[default]
+    Photographic,
+}
+
+/// How sharp the photographic textures are, trading video memory for close-ups.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Sequence)]
+pub enum TextureDetail {
+    /// Small textures for everything.
+    Low,
+    /// Full size textures for the selected object, and small ones for the rest.
+    High,
+    /// High if the GPU can hold the biggest textures, low otherwise.
+    #[default]
+    Auto,
+}
+
+/// The width of the biggest photographic textures, which [`TextureDetail::Auto`] checks the GPU
+/// can hold.
+const FULL_TEXTURE_SIZE: u32 = 8192;
+
+impl TextureDetail {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Low => "Low",
+            Self::High => "High when selected",
+            Self::Auto => "Automatic",
+        }
+    }
+
+    /// Whether the selected object gets its full size texture on this GPU.
+    fn is_sharp(self, device: &RenderDevice) -> bool {
+        match self {
+            Self::Low => false,
+            Self::High => true,
+            Self::Auto => device.limits().max_texture_dimension_2d >= FULL_TEXTURE_SIZE,
+        }
+    }
+}
+
+impl TexturePack {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Photographic => "NASA photographic",
+        }
+    }
+}
+
+/// Where each object's images are, compiled into the binary.
+#[derive(Resource, Debug, Clone)]
+pub struct TextureFiles {
+    /// The texture for each object in each pack, from `assets/texture_packs.ron`.
+    packs: HashMap<TexturePack, HashMap<SpaceObject, String>>,
+    /// The smaller copies of the photographic textures, from `assets/low_detail_textures.ron`.
+    low_detail: HashMap<SpaceObject, String>,
+}
+
+impl Default for TextureFiles {
+    fn default() -> Self {
+        Self {
+            packs: ron::from_str(include_str!("../assets/texture_packs.ron"))
+                .unwrap_or_else(|err| panic!("invalid texture packs: {err}")),
+            low_detail: ron::from_str(include_str!("../assets/low_detail_textures.ron"))
+                .unwrap_or_else(|err| panic!("invalid low detail textures: {err}")),
+        }
+    }
+}
+
+impl TextureFiles {
+    /// The path to `obj`'s texture in `pack`, falling back to the photographic one.
+    /// Photographic textures come in a smaller copy unless `sharp` is set.
+    /// Objects with no texture in any pack are drawn with a plain color instead.
+    pub fn texture(&self, pack: TexturePack, obj: SpaceObject, sharp: bool) -> Option<&str> {
+        let find = |pack: TexturePack| self.packs.get(&pack)?.get(&obj);
+        if let Some(path) = find(pack).filter(|_| pack != TexturePack::Photographic) {
+            return Some(path);
+        }
+
+        self.low_detail
+            .get(&obj)
+            .filter(|_| !sharp)
+            .or_else(|| find(TexturePack::Photographic))
+            .map(String::as_str)
+    }
+}
+
+// point every textured object at its image in the pack, at the detail it should have now that
+// the selection may have moved
+#[allow(clippy::too_many_arguments)]
+pub fn apply_textures(
+    pack: Res<TexturePack>,
+    detail: Res<TextureDetail>,
+    files: Res<TextureFiles>,
+    device: Res<RenderDevice>,
+    asset_server: Res<AssetServer>,
+    current: Query<&SpaceObject, With<CurrentObject>>,
+    objs: Query<(&SpaceObject, &ObjectMaterials)>,
+    sun: Query<&SunMaterials>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut sun_materials: ResMut<Assets<SunMaterial>>,
+    mut shown: Local<HashMap<SpaceObject, String>>,
+    mut loading: Local<Vec<(SpaceObject, Handle<Image>)>>,
+) {
+    let sharp = detail.is_sharp(&device);
+    let selected = current.get_single().ok().copied();
+    let mut swaps = Vec::new();
+
+    for obj in enum_iterator::all::<SpaceObject>() {
+        let Some(path) = files.texture(*pack, obj, sharp && selected == Some(obj)) else {
+            continue;
+        };
+        // everything is spawned with its small photographic texture
+        let spawned = files.texture(TexturePack::Photographic, obj, false);
+        let was = shown
+            .entry(obj)
+            .or_insert_with(|| spawned.unwrap_or(path).to_string());
+        if *was == path {
+            continue;
+        }
+        *was = path.to_string();
+
+        let image = asset_server.load(path);
+        loading.retain(|(other, _)| *other != obj);
+        if Some(path) != files.texture(TexturePack::Photographic, obj, true) {
+            loading.push((obj, image.clone()));
+        }
+        swaps.push((obj, image));
+    }
+
+    // an image missing from the pack only shows up once it fails to load
+    loading.retain(|(obj, image)| match asset_server.get_load_state(image) {
+        LoadState::Failed => {
+            warn!("no {} texture for {obj:?}", pack.name());
+            if let Some(path) = files.texture(TexturePack::Photographic, *obj, true) {
+                swaps.push((*obj, asset_server.load(path)));
+            }
+            false
+        }
+        LoadState::Loaded | LoadState::Unloaded => false,
+        LoadState::NotLoaded | LoadState::Loading => true,
+    });
+
+    for (obj, image) in swaps {
+        if obj == SpaceObject::Sun {
+            for handles in sun.iter() {
+                if let Some(material) = sun_materials.get_mut(&handles.textured) {
+                    material.texture = image.clone();
+                }
+            }
+            continue;
+        }
+
+        for (_, obj_materials) in objs.iter().filter(|(other, _)| **other == obj) {
+            // objects colored by the palette stay untextured
+            let textured = materials
+                .get(&obj_materials.textured)
+                .map_or(false, |material| material.base_color_texture.is_some());
+            if let Some(material) = materials
+                .get_mut(&obj_materials.textured)
+                .filter(|_| textured)
+            {
+                material.base_color_texture = Some(image.clone());
+            }
+        }
+    }
+}
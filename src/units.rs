@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{scientific_notation, settings::Persisted, with_separators};
+
+/// Which units masses, sizes and temperatures are shown in. Distances in AU and times in days
+/// are the same everywhere, so they're left alone.
+#[derive(
+    Resource,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    enum_iterator::Sequence,
+)]
+pub enum UnitSystem {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+impl Persisted for UnitSystem {
+    const KEY: &'static str = "unit_system";
+}
+
+/// How many miles one kilometer is.
+const MILES_PER_KM: f32 = 0.621_371;
+
+/// How many feet one meter is.
+const FEET_PER_METER: f32 = 3.280_84;
+
+/// How many pounds one kilogram is.
+const POUNDS_PER_KG: f32 = 2.204_623;
+
+/// How many pounds per cubic foot one kilogram per cubic meter is.
+const POUNDS_PER_CUBIC_FOOT_PER_KG_PER_CUBIC_METER: f32 = 0.062_428;
+
+pub fn km_to_miles(km: f32) -> f32 {
+    km * MILES_PER_KM
+}
+
+pub fn meters_to_feet(meters: f32) -> f32 {
+    meters * FEET_PER_METER
+}
+
+pub fn kg_to_pounds(kg: f32) -> f32 {
+    kg * POUNDS_PER_KG
+}
+
+pub fn kg_per_cubic_meter_to_pounds_per_cubic_foot(density: f32) -> f32 {
+    density * POUNDS_PER_CUBIC_FOOT_PER_KG_PER_CUBIC_METER
+}
+
+pub fn celsius_to_fahrenheit(celsius: f32) -> f32 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+impl UnitSystem {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Metric => "Metric",
+            Self::Imperial => "Imperial",
+        }
+    }
+
+    /// `kg` written out with its unit.
+    pub fn mass(self, kg: f32) -> String {
+        match self {
+            Self::Metric => format!("{} kg", scientific_notation(kg)),
+            Self::Imperial => format!("{} lb", scientific_notation(kg_to_pounds(kg))),
+        }
+    }
+
+    /// `km` written out with its unit.
+    pub fn length(self, km: f32) -> String {
+        match self {
+            Self::Metric => format!("{} km", with_separators(km)),
+            Self::Imperial => format!("{} mi", with_separators(km_to_miles(km).round())),
+        }
+    }
+
+    /// `km_per_second` written out with its unit.
+    pub fn speed(self, km_per_second: f32) -> String {
+        match self {
+            Self::Metric => format!("{km_per_second:.1} km/s"),
+            Self::Imperial => format!("{:.1} mi/s", km_to_miles(km_per_second)),
+        }
+    }
+
+    /// An acceleration of `meters_per_second_squared` written out with its unit.
+    pub fn acceleration(self, meters_per_second_squared: f32) -> String {
+        match self {
+            Self::Metric => format!("{meters_per_second_squared:.2} m/s²"),
+            Self::Imperial => format!("{:.2} ft/s²", meters_to_feet(meters_per_second_squared)),
+        }
+    }
+
+    /// A density of `kg_per_cubic_meter` written out with its unit.
+    pub fn density(self, kg_per_cubic_meter: f32) -> String {
+        match self {
+            Self::Metric => format!("{} kg/m³", with_separators(kg_per_cubic_meter.round())),
+            Self::Imperial => format!(
+                "{:.1} lb/ft³",
+                kg_per_cubic_meter_to_pounds_per_cubic_foot(kg_per_cubic_meter)
+            ),
+        }
+    }
+
+    /// `celsius` written out with its unit.
+    pub fn temperature(self, celsius: f32) -> String {
+        match self {
+            Self::Metric => format!("{celsius}°C"),
+            Self::Imperial => format!("{:.0}°F", celsius_to_fahrenheit(celsius)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn earths_diameter_in_miles() {
+        assert!((km_to_miles(12_742.0) - 7_918.0).abs() < 1.0);
+        assert_eq!(UnitSystem::Imperial.length(12_742.0), "7,918 mi");
+        assert_eq!(UnitSystem::Metric.length(12_742.0), "12,742 km");
+    }
+
+    #[test]
+    fn temperatures_in_fahrenheit() {
+        assert_eq!(celsius_to_fahrenheit(0.0), 32.0);
+        assert_eq!(celsius_to_fahrenheit(100.0), 212.0);
+        assert_eq!(celsius_to_fahrenheit(-40.0), -40.0);
+    }
+
+    #[test]
+    fn masses_in_pounds() {
+        assert!((kg_to_pounds(1.0) - 2.205).abs() < 1e-3);
+        assert!((kg_to_pounds(5.972e24) / 1.3166e25 - 1.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+use bevy_egui::EguiContext;
+
+use crate::{
+    selection::{object_selected, CurrentObject},
+    share,
+};
+
+/// Moving in on and back out from the selected object, with the keyboard, the scroll wheel or a
+/// pinch.
+pub struct ZoomPlugin;
+
+impl Plugin for ZoomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraZoom>()
+            .add_system(keyboard_zoom)
+            .add_system(pinch_zoom)
+            .add_system(
+                reset_zoom
+                    .after(object_selected)
+                    .before(share::apply_scene_state),
+            )
+            .add_system(scroll_zoom.after(reset_zoom));
+    }
+}
+
+/// How far the camera sits from the locked object, as a multiple of its radius.
+/// Back to [`Self::DEFAULT`] whenever something new is selected.
+#[derive(Resource)]
+pub struct CameraZoom(pub f32);
+
+impl CameraZoom {
+    pub const DEFAULT: f32 = 3.0;
+    // never let the camera end up inside the object
+    pub const MIN: f32 = 1.2;
+    const MAX: f32 = 20.0;
+
+    /// Multiplies the zoom by `factor`, keeping it within [`Self::MIN`] and [`Self::MAX`].
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.0 = (self.0 * factor).clamp(Self::MIN, Self::MAX);
+    }
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self(Self::DEFAULT)
+    }
+}
+
+/// How much the zoom changes per second while a zoom key is held.
+const KEYBOARD_ZOOM_SPEED: f32 = 2.0;
+
+// `+`/`=` zooms in and `-` zooms out, for touchpads where scrolling is awkward
+pub fn keyboard_zoom(time: Res<Time>, kbd: Res<Input<KeyCode>>, mut zoom: ResMut<CameraZoom>) {
+    let factor = KEYBOARD_ZOOM_SPEED.powf(time.delta_seconds());
+
+    if kbd.any_pressed([KeyCode::Plus, KeyCode::Equals, KeyCode::NumpadAdd]) {
+        zoom.zoom_by(1.0 / factor);
+    }
+    if kbd.any_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        zoom.zoom_by(factor);
+    }
+}
+
+/// How much one notch of the scroll wheel zooms by.
+const SCROLL_ZOOM_STEP: f32 = 1.1;
+
+/// How many pixels of smooth scrolling, like on a touchpad, count as one notch.
+const PIXELS_PER_SCROLL_LINE: f32 = 50.0;
+
+// scrolling up moves in closer to the selected object, scrolling down pulls back to see its moons
+pub fn scroll_zoom(
+    mut wheel: EventReader<MouseWheel>,
+    mut egui_ctx: ResMut<EguiContext>,
+    current: Query<(), With<CurrentObject>>,
+    mut zoom: ResMut<CameraZoom>,
+) {
+    let lines: f32 = wheel
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_SCROLL_LINE,
+        })
+        .sum();
+    // scrolling over a window scrolls the window
+    if lines == 0.0 || current.is_empty() || egui_ctx.ctx_mut().is_pointer_over_area() {
+        return;
+    }
+
+    zoom.zoom_by(SCROLL_ZOOM_STEP.powf(-lines));
+}
+
+// start every newly selected object from the usual distance. A shared view sets its own zoom
+// along with its selection, which is left alone
+fn reset_zoom(selected: Query<(), Added<CurrentObject>>, mut zoom: ResMut<CameraZoom>) {
+    if !selected.is_empty() && !zoom.is_changed() {
+        *zoom = CameraZoom::default();
+    }
+}
+
+// pinching two fingers together zooms out, spreading them apart zooms in
+pub fn pinch_zoom(touches: Res<Touches>, mut zoom: ResMut<CameraZoom>) {
+    let mut pressed = touches.iter();
+    let (Some(first), Some(second), None) = (pressed.next(), pressed.next(), pressed.next()) else {
+        return;
+    };
+
+    let previous = first
+        .previous_position()
+        .distance(second.previous_position());
+    let current = first.position().distance(second.position());
+
+    if previous > 0.0 && current > 0.0 {
+        zoom.zoom_by(previous / current);
+    }
+}